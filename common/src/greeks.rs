@@ -0,0 +1,33 @@
+use serde::{Serialize, Deserialize};
+
+use crate::BlackScholesInput;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum GreekMethod {
+    /// Closed-form Black-Scholes sensitivities.
+    Analytic,
+    /// One pass of the pricer over dual numbers seeded with respect to the input.
+    DualNumber,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GreeksInput {
+    pub option: BlackScholesInput,
+    pub method: GreekMethod,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Greek {
+    pub value: f64,
+    pub method: GreekMethod,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Greeks {
+    pub call: f64,
+    pub put: f64,
+    pub call_delta: Greek,
+    pub put_delta: Greek,
+    /// Shared by the call and the put.
+    pub vega: Greek,
+}
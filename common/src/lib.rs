@@ -0,0 +1,260 @@
+//! The types the program and the script exchange: the requests the program is asked to prove,
+//! the values it commits, and the bounds policy requests are checked against. Both sides
+//! encode them with bincode, so there is exactly one definition of each. The crate is
+//! `no_std` and allocates through `alloc` only, so the program can depend on it without
+//! pulling in anything host-side.
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+mod greeks;
+mod parity;
+mod policy;
+mod quanto;
+mod term_structure;
+mod theta;
+mod volatility;
+
+use alloc::vec::Vec;
+use serde::{Serialize, Deserialize};
+
+pub use greeks::{Greek, GreekMethod, Greeks, GreeksInput};
+pub use parity::{ImpliedDividend, ImpliedDividendError, ImpliedDividendInput};
+pub use policy::{BoundViolation, BoundsPolicy};
+pub use quanto::{QuantoError, QuantoInput, QuantoPrices};
+pub use term_structure::{TermPoint, TermStructure, TermStructureError, MAX_TERM_POINTS};
+#[cfg(feature = "host")]
+pub use term_structure::compute_outputs_digest;
+pub use theta::{ThetaError, ThetaPoint, ThetaSchedule, ThetaScheduleInput, MAX_THETA_OFFSETS};
+pub use volatility::{
+    EwmaInput, GarchInput, GarchParams, PriceSeries, SeriesError, VolEstimate, MAX_SERIES_LEN,
+    MIN_SERIES_LEN,
+};
+
+/// Layout version of the committed public values, committed first by every proof. Bump it with
+/// any change to what is committed or in what order; `script/snapshots/README.md` has the
+/// procedure.
+pub const SCHEMA_VERSION: u32 = 4;
+
+/// The finalized Avail block the script submitted the request blob in. The program cannot
+/// check it; it is echoed so that a verifier can check inclusion of the committed request hash
+/// off-circuit.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaInclusion {
+    pub block_hash: [u8; 32],
+    pub block_number: u32,
+}
+
+/// What every proof commits ahead of its outputs, in commit order. The outputs follow only if
+/// `check` passed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Header {
+    pub schema_version: u32,
+    /// sha256 of the request bytes the program read, which are the submitted blob.
+    pub input_hash: [u8; 32],
+    pub inclusion: DaInclusion,
+    /// sha256 of the bincode-encoded [`BoundsPolicy`] the request was checked against.
+    pub policy_hash: [u8; 32],
+    pub check: Result<(), BoundViolation>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlackScholesInput {
+    pub price: f64,
+    pub strike: f64,
+    pub iv: f64,
+    pub time: f64,
+    pub rate: f64,
+}
+
+impl Default for BlackScholesInput {
+    /// The option the script prices when it is given no request.
+    fn default() -> Self {
+        BlackScholesInput {
+            price: 100.0,
+            strike: 105.0,
+            iv: 0.2,
+            time: 1.0,
+            rate: 0.05,
+        }
+    }
+}
+
+/// What the program is asked to prove. Every proof first commits a [`Header`]; the values
+/// committed after an accepted policy check depend on the variant.
+///
+/// Its bincode encoding is a u32 variant tag followed by the variant's fields. The only length
+/// prefixes are a term structure's, a price series' and a theta schedule's, and decoding
+/// allocates for at most as many elements as the blob actually holds; pricing then rejects
+/// more than [`MAX_TERM_POINTS`] options, [`MAX_SERIES_LEN`] closes or [`MAX_THETA_OFFSETS`]
+/// offsets with a committed error. Bytes that do not decode panic the program, and no proof
+/// is produced for them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum PricingRequest {
+    /// Commits the call and put prices.
+    BlackScholes(BlackScholesInput),
+    /// Commits the quotes followed by the implied dividend yield or the reason none exists.
+    ImpliedDividend(ImpliedDividendInput),
+    /// Commits the per-option prices, the (tenor, call, forward) term structure and a
+    /// calendar-arbitrage flag followed by their [`compute_outputs_digest`], or the reason the
+    /// list was rejected. All options must share one spot and one strike.
+    TermStructure(Vec<BlackScholesInput>),
+    /// Commits the inputs followed by the quanto call, put and deltas or the rejection.
+    Quanto(QuantoInput),
+    /// Commits the inputs followed by the call, put, deltas and vega, each Greek tagged with
+    /// the method that produced it.
+    Greeks(GreeksInput),
+    /// Commits the sha256 of the price series and lambda, followed by the estimated vol and
+    /// the call and put priced with it, or the reason the series was rejected.
+    Ewma(EwmaInput),
+    /// Commits the sha256 of the GARCH(1,1) parameters and of the price series and the
+    /// forecast horizon, followed by the forecast vol and the call and put priced with it, or
+    /// the reason the series or parameters were rejected.
+    Garch(GarchInput),
+    /// Commits today's call and put followed by the (offset, call, put) schedule at each
+    /// forward offset, or the rejection.
+    ThetaSchedule(ThetaScheduleInput),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// The number of [`PricingRequest`] variants.
+    const VARIANTS: u32 = 8;
+
+    fn any_option() -> impl Strategy<Value = BlackScholesInput> {
+        any::<[f64; 5]>().prop_map(|[price, strike, iv, time, rate]| BlackScholesInput {
+            price,
+            strike,
+            iv,
+            time,
+            rate,
+        })
+    }
+
+    fn any_request() -> impl Strategy<Value = PricingRequest> {
+        prop_oneof![
+            any_option().prop_map(PricingRequest::BlackScholes),
+            any::<[f64; 6]>().prop_map(|[call, put, price, strike, time, rate]| {
+                PricingRequest::ImpliedDividend(ImpliedDividendInput {
+                    call,
+                    put,
+                    price,
+                    strike,
+                    time,
+                    rate,
+                })
+            }),
+            prop::collection::vec(any_option(), 0..8).prop_map(PricingRequest::TermStructure),
+            any::<[f64; 9]>().prop_map(
+                |[price, strike, iv, time, domestic_rate, foreign_rate, fx_vol, correlation, conversion_rate]| {
+                    PricingRequest::Quanto(QuantoInput {
+                        price,
+                        strike,
+                        iv,
+                        time,
+                        domestic_rate,
+                        foreign_rate,
+                        fx_vol,
+                        correlation,
+                        conversion_rate,
+                    })
+                },
+            ),
+            (any_option(), any::<bool>()).prop_map(|(option, analytic)| {
+                let method = if analytic { GreekMethod::Analytic } else { GreekMethod::DualNumber };
+                PricingRequest::Greeks(GreeksInput { option, method })
+            }),
+            (any::<[f64; 6]>(), prop::collection::vec(any::<f64>(), 0..16)).prop_map(
+                |([price, strike, time, rate, periods_per_year, lambda], closes)| {
+                    PricingRequest::Ewma(EwmaInput {
+                        price,
+                        strike,
+                        time,
+                        rate,
+                        series: PriceSeries {
+                            closes,
+                            periods_per_year,
+                        },
+                        lambda,
+                    })
+                },
+            ),
+            (any::<[f64; 8]>(), prop::collection::vec(any::<f64>(), 0..16), any::<u32>()).prop_map(
+                |([price, strike, time, rate, periods_per_year, omega, alpha, beta], closes, horizon)| {
+                    PricingRequest::Garch(GarchInput {
+                        price,
+                        strike,
+                        time,
+                        rate,
+                        series: PriceSeries {
+                            closes,
+                            periods_per_year,
+                        },
+                        params: GarchParams { omega, alpha, beta },
+                        horizon,
+                    })
+                },
+            ),
+            (any_option(), prop::collection::vec(any::<u32>(), 0..24)).prop_map(
+                |(option, offsets_days)| {
+                    PricingRequest::ThetaSchedule(ThetaScheduleInput { option, offsets_days })
+                },
+            ),
+        ]
+    }
+
+    /// A variant tag, valid or one past the last, followed by arbitrary bytes.
+    fn garbage() -> impl Strategy<Value = Vec<u8>> {
+        (0..=VARIANTS, prop::collection::vec(any::<u8>(), 0..128)).prop_map(|(tag, tail)| {
+            let mut bytes = tag.to_le_bytes().to_vec();
+            bytes.extend(tail);
+            bytes
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn requests_round_trip_bit_for_bit(request in any_request()) {
+            let bytes = bincode::serialize(&request).unwrap();
+            let decoded = bincode::deserialize::<PricingRequest>(&bytes).unwrap();
+            // Compared as bytes so that NaN fields count as equal.
+            prop_assert_eq!(bincode::serialize(&decoded).unwrap(), bytes);
+        }
+
+        #[test]
+        fn garbage_never_decodes_past_itself(bytes in garbage()) {
+            // Decoding never panics, and whatever decodes re-encodes to the bytes it read.
+            if let Ok(request) = bincode::deserialize::<PricingRequest>(&bytes) {
+                prop_assert!(bytes.starts_with(&bincode::serialize(&request).unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn black_scholes_requests_have_a_fixed_size() {
+        let request = PricingRequest::BlackScholes(BlackScholesInput::default());
+        let bytes = bincode::serialize(&request).unwrap();
+        // The variant tag and five f64 fields; any shorter prefix fails to decode.
+        assert_eq!(bytes.len(), 4 + 5 * 8);
+        for len in 0..bytes.len() {
+            assert!(bincode::deserialize::<PricingRequest>(&bytes[..len]).is_err());
+        }
+    }
+
+    #[test]
+    fn length_prefixes_past_the_blob_fail_to_decode() {
+        // A term structure claiming u64::MAX options, and one claiming one more than it holds.
+        let mut huge = 2u32.to_le_bytes().to_vec();
+        huge.extend(u64::MAX.to_le_bytes());
+        huge.extend([0; 40]);
+        assert!(bincode::deserialize::<PricingRequest>(&huge).is_err());
+
+        let options = vec![BlackScholesInput::default(); 3];
+        let mut short = bincode::serialize(&PricingRequest::TermStructure(options)).unwrap();
+        short[4] += 1;
+        assert!(bincode::deserialize::<PricingRequest>(&short).is_err());
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Serialize, Deserialize};
+
+/// Simultaneous call and put quotes at the same strike and expiry.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImpliedDividendInput {
+    pub call: f64,
+    pub put: f64,
+    pub price: f64,
+    pub strike: f64,
+    pub time: f64,
+    pub rate: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImpliedDividend {
+    /// Continuous dividend yield q solving `C - P = s·e^{-qt} - k·e^{-rt}`.
+    pub dividend_yield: f64,
+    /// Parity re-evaluated at the solved yield; zero up to rounding.
+    pub residual: f64,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum ImpliedDividendError {
+    /// A field is not finite, a quote is negative, or price, strike or time is not positive.
+    InvalidInput,
+    /// The quotes break the no-arbitrage bounds (`P <= k·e^{-rt}`, `s·e^{-qt} > 0`), so no
+    /// real dividend yield satisfies parity.
+    ArbitrageBoundViolated,
+}
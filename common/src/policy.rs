@@ -0,0 +1,321 @@
+use core::cmp::Ordering;
+use serde::{Serialize, Deserialize};
+
+use crate::PricingRequest;
+
+/// Approved input ranges. Every priced field is checked against these before any pricing
+/// happens; the policy itself is committed by hash.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BoundsPolicy {
+    pub min_price: f64,
+    pub max_price: f64,
+    pub min_strike: f64,
+    pub max_strike: f64,
+    pub min_iv: f64,
+    pub max_iv: f64,
+    pub min_time: f64,
+    pub max_time: f64,
+    /// Applies to every rate in the request, in both directions.
+    pub max_abs_rate: f64,
+}
+
+/// The first bound a request violates. Non-finite fields violate the bound checked first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoundViolation {
+    PriceBelowMin,
+    PriceAboveMax,
+    StrikeBelowMin,
+    StrikeAboveMax,
+    IvBelowMin,
+    IvAboveMax,
+    TimeBelowMin,
+    TimeAboveMax,
+    RateAboveMaxAbs,
+}
+
+/// `min <= value`, spelled through `partial_cmp` so that NaN, which is unordered, fails it.
+fn at_least(value: f64, min: f64) -> bool {
+    matches!(value.partial_cmp(&min), Some(Ordering::Greater | Ordering::Equal))
+}
+
+/// `value <= max`, failed by NaN.
+fn at_most(value: f64, max: f64) -> bool {
+    matches!(value.partial_cmp(&max), Some(Ordering::Less | Ordering::Equal))
+}
+
+/// Fails with `below` or `above` unless `min <= value <= max`, which NaN never is.
+fn check(
+    value: f64,
+    min: f64,
+    max: f64,
+    below: BoundViolation,
+    above: BoundViolation,
+) -> Result<(), BoundViolation> {
+    if !at_least(value, min) {
+        return Err(below);
+    }
+    if !at_most(value, max) {
+        return Err(above);
+    }
+    Ok(())
+}
+
+impl BoundsPolicy {
+    fn check_price(&self, price: f64) -> Result<(), BoundViolation> {
+        check(
+            price,
+            self.min_price,
+            self.max_price,
+            BoundViolation::PriceBelowMin,
+            BoundViolation::PriceAboveMax,
+        )
+    }
+
+    fn check_strike(&self, strike: f64) -> Result<(), BoundViolation> {
+        check(
+            strike,
+            self.min_strike,
+            self.max_strike,
+            BoundViolation::StrikeBelowMin,
+            BoundViolation::StrikeAboveMax,
+        )
+    }
+
+    fn check_iv(&self, iv: f64) -> Result<(), BoundViolation> {
+        check(
+            iv,
+            self.min_iv,
+            self.max_iv,
+            BoundViolation::IvBelowMin,
+            BoundViolation::IvAboveMax,
+        )
+    }
+
+    fn check_time(&self, time: f64) -> Result<(), BoundViolation> {
+        check(
+            time,
+            self.min_time,
+            self.max_time,
+            BoundViolation::TimeBelowMin,
+            BoundViolation::TimeAboveMax,
+        )
+    }
+
+    fn check_rate(&self, rate: f64) -> Result<(), BoundViolation> {
+        // Both sides rather than `rate.abs()`, which core does not provide.
+        if !at_least(rate, -self.max_abs_rate) || !at_most(rate, self.max_abs_rate) {
+            return Err(BoundViolation::RateAboveMaxAbs);
+        }
+        Ok(())
+    }
+
+    /// Checks a vol the program estimated itself against the same range as a quoted one.
+    pub fn check_estimated_vol(&self, vol: f64) -> Result<(), BoundViolation> {
+        self.check_iv(vol)
+    }
+
+    fn check_option(
+        &self,
+        price: f64,
+        strike: f64,
+        iv: f64,
+        time: f64,
+        rate: f64,
+    ) -> Result<(), BoundViolation> {
+        self.check_price(price)?;
+        self.check_strike(strike)?;
+        self.check_iv(iv)?;
+        self.check_time(time)?;
+        self.check_rate(rate)
+    }
+
+    /// Checks every priced field of `request`. The program runs this before pricing and its
+    /// result is authoritative; the script runs it before submitting, to avoid proving a
+    /// rejection.
+    pub fn validate(&self, request: &PricingRequest) -> Result<(), BoundViolation> {
+        match request {
+            PricingRequest::BlackScholes(option) => self.check_option(
+                option.price,
+                option.strike,
+                option.iv,
+                option.time,
+                option.rate,
+            ),
+            PricingRequest::ImpliedDividend(input) => {
+                self.check_price(input.price)?;
+                self.check_strike(input.strike)?;
+                self.check_time(input.time)?;
+                self.check_rate(input.rate)
+            }
+            PricingRequest::TermStructure(options) => options.iter().try_for_each(|option| {
+                self.check_option(
+                    option.price,
+                    option.strike,
+                    option.iv,
+                    option.time,
+                    option.rate,
+                )
+            }),
+            PricingRequest::Quanto(input) => {
+                self.check_option(
+                    input.price,
+                    input.strike,
+                    input.iv,
+                    input.time,
+                    input.domestic_rate,
+                )?;
+                self.check_rate(input.foreign_rate)?;
+                // A zero FX vol is a legitimate input below any minimum underlying vol.
+                if !at_most(input.fx_vol, self.max_iv) {
+                    return Err(BoundViolation::IvAboveMax);
+                }
+                Ok(())
+            }
+            PricingRequest::Greeks(input) => {
+                let option = &input.option;
+                self.check_option(
+                    option.price,
+                    option.strike,
+                    option.iv,
+                    option.time,
+                    option.rate,
+                )
+            }
+            // The vol is estimated in the program and checked there, through
+            // `check_estimated_vol`.
+            PricingRequest::Ewma(input) => {
+                self.check_price(input.price)?;
+                self.check_strike(input.strike)?;
+                self.check_time(input.time)?;
+                self.check_rate(input.rate)
+            }
+            PricingRequest::Garch(input) => {
+                self.check_price(input.price)?;
+                self.check_strike(input.strike)?;
+                self.check_time(input.time)?;
+                self.check_rate(input.rate)
+            }
+            PricingRequest::ThetaSchedule(input) => {
+                let option = &input.option;
+                self.check_option(
+                    option.price,
+                    option.strike,
+                    option.iv,
+                    option.time,
+                    option.rate,
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlackScholesInput, QuantoInput};
+
+    fn policy() -> BoundsPolicy {
+        BoundsPolicy {
+            min_price: 1.0,
+            max_price: 1000.0,
+            min_strike: 1.0,
+            max_strike: 1000.0,
+            min_iv: 0.01,
+            max_iv: 5.0,
+            min_time: 0.01,
+            max_time: 5.0,
+            max_abs_rate: 0.25,
+        }
+    }
+
+    /// The floats immediately below and above a positive `value`.
+    fn neighbours(value: f64) -> (f64, f64) {
+        (f64::from_bits(value.to_bits() - 1), f64::from_bits(value.to_bits() + 1))
+    }
+
+    type Setter = fn(&mut BlackScholesInput, f64);
+
+    fn validate_with(set: Setter, value: f64) -> Result<(), BoundViolation> {
+        let mut option = BlackScholesInput::default();
+        set(&mut option, value);
+        policy().validate(&PricingRequest::BlackScholes(option))
+    }
+
+    #[test]
+    fn bounds_are_inclusive_and_reject_nan() {
+        let policy = policy();
+        let fields: [(Setter, f64, f64, BoundViolation, BoundViolation); 4] = [
+            (
+                |option, value| option.price = value,
+                policy.min_price,
+                policy.max_price,
+                BoundViolation::PriceBelowMin,
+                BoundViolation::PriceAboveMax,
+            ),
+            (
+                |option, value| option.strike = value,
+                policy.min_strike,
+                policy.max_strike,
+                BoundViolation::StrikeBelowMin,
+                BoundViolation::StrikeAboveMax,
+            ),
+            (
+                |option, value| option.iv = value,
+                policy.min_iv,
+                policy.max_iv,
+                BoundViolation::IvBelowMin,
+                BoundViolation::IvAboveMax,
+            ),
+            (
+                |option, value| option.time = value,
+                policy.min_time,
+                policy.max_time,
+                BoundViolation::TimeBelowMin,
+                BoundViolation::TimeAboveMax,
+            ),
+        ];
+        for (set, min, max, below, above) in fields {
+            assert_eq!(validate_with(set, neighbours(min).0), Err(below));
+            assert_eq!(validate_with(set, min), Ok(()));
+            assert_eq!(validate_with(set, max), Ok(()));
+            assert_eq!(validate_with(set, neighbours(max).1), Err(above));
+            assert_eq!(validate_with(set, f64::NAN), Err(below));
+        }
+    }
+
+    #[test]
+    fn rate_bound_applies_in_both_directions() {
+        let set: Setter = |option, value| option.rate = value;
+        let max = policy().max_abs_rate;
+        let beyond = neighbours(max).1;
+        for rate in [-max, 0.0, max] {
+            assert_eq!(validate_with(set, rate), Ok(()));
+        }
+        for rate in [-beyond, beyond, f64::NAN] {
+            assert_eq!(validate_with(set, rate), Err(BoundViolation::RateAboveMaxAbs));
+        }
+    }
+
+    #[test]
+    fn quanto_fx_vol_is_bounded_above_only() {
+        let quanto = |fx_vol| {
+            PricingRequest::Quanto(QuantoInput {
+                price: 100.0,
+                strike: 105.0,
+                iv: 0.2,
+                time: 1.0,
+                domestic_rate: 0.05,
+                foreign_rate: 0.02,
+                fx_vol,
+                correlation: 0.3,
+                conversion_rate: 1.0,
+            })
+        };
+        let policy = policy();
+        let beyond = neighbours(policy.max_iv).1;
+        assert_eq!(policy.validate(&quanto(0.0)), Ok(()));
+        assert_eq!(policy.validate(&quanto(policy.max_iv)), Ok(()));
+        assert_eq!(policy.validate(&quanto(beyond)), Err(BoundViolation::IvAboveMax));
+        assert_eq!(policy.validate(&quanto(f64::NAN)), Err(BoundViolation::IvAboveMax));
+    }
+}
@@ -0,0 +1,37 @@
+use serde::{Serialize, Deserialize};
+
+/// A European option on a foreign underlying, paid in domestic currency at a fixed
+/// conversion rate.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuantoInput {
+    /// Spot of the underlying, in foreign currency.
+    pub price: f64,
+    /// Strike, in foreign currency.
+    pub strike: f64,
+    pub iv: f64,
+    pub time: f64,
+    pub domestic_rate: f64,
+    pub foreign_rate: f64,
+    pub fx_vol: f64,
+    /// Correlation between the underlying and the FX rate, in [-1, 1].
+    pub correlation: f64,
+    /// Domestic units paid per foreign unit of payoff.
+    pub conversion_rate: f64,
+}
+
+/// Prices and spot deltas, in domestic currency.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuantoPrices {
+    pub call: f64,
+    pub put: f64,
+    pub call_delta: f64,
+    pub put_delta: f64,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum QuantoError {
+    /// A field is not finite, the FX vol is negative, or price, strike, vol, time or the
+    /// conversion rate is not positive.
+    InvalidInput,
+    CorrelationOutOfRange,
+}
@@ -0,0 +1,48 @@
+use alloc::vec::Vec;
+use serde::{Serialize, Deserialize};
+
+/// Upper bound on the expiries in one term structure, which bounds the cycles proving it costs.
+pub const MAX_TERM_POINTS: usize = 64;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TermPoint {
+    pub time: f64,
+    pub call: f64,
+    /// Forward implied by the option's spot and rate pillar, `s·e^{rt}`.
+    pub forward: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TermStructure {
+    /// Call and put price of every option, in input order.
+    pub prices: Vec<(f64, f64)>,
+    /// The call term structure, ordered by tenor.
+    pub points: Vec<TermPoint>,
+    /// Set when a longer-dated call is cheaper than a shorter-dated one whose strike is
+    /// discounted by no more, `r_long·t_long >= r_short·t_short`. Other pairs are not compared:
+    /// with a negative or sharply falling rate the longer-dated strike is discounted by less,
+    /// and its call can legitimately be cheaper.
+    pub calendar_arbitrage: bool,
+}
+
+/// The digest a proof commits after an accepted term structure: sha256 of the structure's
+/// bincode encoding, which is the canonical one. A contract can check outputs supplied
+/// off-chain against these 32 bytes instead of decoding them.
+#[cfg(feature = "host")]
+pub fn compute_outputs_digest(outputs: &TermStructure) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(bincode::serialize(outputs).unwrap()).into()
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum TermStructureError {
+    Empty,
+    /// More than [`MAX_TERM_POINTS`] expiries.
+    TooLong,
+    /// The options do not all share the first option's strike.
+    MismatchedStrike,
+    /// The options do not all share the first option's spot, so they are not one underlying.
+    MismatchedSpot,
+    /// Two options share a tenor, which leaves the term structure ambiguous.
+    DuplicateTenor,
+}
@@ -0,0 +1,35 @@
+use alloc::vec::Vec;
+use serde::{Serialize, Deserialize};
+
+use crate::BlackScholesInput;
+
+/// Longest accepted list of forward offsets, which bounds the cycles proving a schedule costs.
+pub const MAX_THETA_OFFSETS: usize = 16;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ThetaScheduleInput {
+    pub option: BlackScholesInput,
+    /// Forward valuation dates, in days from today.
+    pub offsets_days: Vec<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ThetaPoint {
+    pub offset_days: u32,
+    pub call: f64,
+    pub put: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ThetaSchedule {
+    pub call: f64,
+    pub put: f64,
+    /// The option's value at each offset with everything but the tenor held fixed.
+    pub points: Vec<ThetaPoint>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThetaError {
+    /// More than [`MAX_THETA_OFFSETS`] offsets.
+    TooManyOffsets,
+}
@@ -0,0 +1,79 @@
+use alloc::vec::Vec;
+use serde::{Serialize, Deserialize};
+
+use crate::BoundViolation;
+
+/// Shortest accepted series of closes.
+pub const MIN_SERIES_LEN: usize = 10;
+/// Longest accepted series of closes, bounding the work done in the zkVM.
+pub const MAX_SERIES_LEN: usize = 1024;
+
+/// Historical closing prices, oldest first.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PriceSeries {
+    pub closes: Vec<f64>,
+    /// Observations per year, used to annualize the per-period volatility (e.g. 252).
+    pub periods_per_year: f64,
+}
+
+/// An option priced with a volatility estimated from a price series.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EwmaInput {
+    pub price: f64,
+    pub strike: f64,
+    pub time: f64,
+    pub rate: f64,
+    pub series: PriceSeries,
+    /// Decay factor in (0, 1]; RiskMetrics uses 0.94 for daily data.
+    pub lambda: f64,
+}
+
+/// GARCH(1,1) parameters, calibrated off-chain, for per-period variance
+/// `h_{t+1} = omega + alpha·r_t² + beta·h_t`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GarchParams {
+    pub omega: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+/// An option priced with a GARCH(1,1) volatility forecast.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GarchInput {
+    pub price: f64,
+    pub strike: f64,
+    pub time: f64,
+    pub rate: f64,
+    pub series: PriceSeries,
+    pub params: GarchParams,
+    /// How many periods past the last close to forecast, at least 1.
+    pub horizon: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VolEstimate {
+    /// The annualized volatility the option was priced with.
+    pub vol: f64,
+    pub call: f64,
+    pub put: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SeriesError {
+    /// Fewer than [`MIN_SERIES_LEN`] closes.
+    TooShort,
+    /// More than [`MAX_SERIES_LEN`] closes.
+    TooLong,
+    /// A close is zero, negative or not finite.
+    NonPositivePrice,
+    InvalidPeriodsPerYear,
+    /// The estimator's own parameter is out of range.
+    InvalidParameter,
+    /// The estimate is zero or not finite, e.g. for a constant series.
+    DegenerateVolatility,
+    /// The estimate is outside the bounds policy's vol range. The policy check before pricing
+    /// only sees the request, so the estimated vol is checked here.
+    OutOfPolicy(BoundViolation),
+    /// `alpha + beta >= 1`, so the variance has no finite unconditional level.
+    NonStationary,
+}
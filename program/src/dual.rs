@@ -0,0 +1,169 @@
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::{norm_cdf, norm_pdf};
+
+/// The operations the pricer needs from its number type, so that one implementation prices
+/// over `f64` and over [`Dual`] alike.
+pub trait Real:
+    Copy
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    fn constant(value: f64) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn norm_cdf(self) -> Self;
+}
+
+impl Real for f64 {
+    fn constant(value: f64) -> Self {
+        value
+    }
+
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+
+    fn ln(self) -> Self {
+        f64::ln(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn norm_cdf(self) -> Self {
+        norm_cdf(self)
+    }
+}
+
+/// A forward-mode dual number: a value together with its derivative with respect to the one
+/// input that was seeded with `Dual::variable`.
+#[derive(Clone, Copy, Debug)]
+pub struct Dual {
+    pub value: f64,
+    pub derivative: f64,
+}
+
+impl Dual {
+    pub fn constant(value: f64) -> Self {
+        Dual {
+            value,
+            derivative: 0.0,
+        }
+    }
+
+    pub fn variable(value: f64) -> Self {
+        Dual {
+            value,
+            derivative: 1.0,
+        }
+    }
+}
+
+impl Real for Dual {
+    fn constant(value: f64) -> Self {
+        Dual::constant(value)
+    }
+
+    fn exp(self) -> Self {
+        let value = self.value.exp();
+        Dual {
+            value,
+            derivative: value * self.derivative,
+        }
+    }
+
+    fn ln(self) -> Self {
+        Dual {
+            value: self.value.ln(),
+            derivative: self.derivative / self.value,
+        }
+    }
+
+    fn sqrt(self) -> Self {
+        let value = self.value.sqrt();
+        Dual {
+            value,
+            derivative: self.derivative / (2.0 * value),
+        }
+    }
+
+    /// The program's normal CDF, differentiated through the exact normal density.
+    fn norm_cdf(self) -> Self {
+        Dual {
+            value: norm_cdf(self.value),
+            derivative: norm_pdf(self.value) * self.derivative,
+        }
+    }
+}
+
+impl From<f64> for Dual {
+    fn from(value: f64) -> Self {
+        Dual::constant(value)
+    }
+}
+
+impl<T: Into<Dual>> Add<T> for Dual {
+    type Output = Dual;
+
+    fn add(self, rhs: T) -> Dual {
+        let rhs = rhs.into();
+        Dual {
+            value: self.value + rhs.value,
+            derivative: self.derivative + rhs.derivative,
+        }
+    }
+}
+
+impl<T: Into<Dual>> Sub<T> for Dual {
+    type Output = Dual;
+
+    fn sub(self, rhs: T) -> Dual {
+        let rhs = rhs.into();
+        Dual {
+            value: self.value - rhs.value,
+            derivative: self.derivative - rhs.derivative,
+        }
+    }
+}
+
+impl<T: Into<Dual>> Mul<T> for Dual {
+    type Output = Dual;
+
+    fn mul(self, rhs: T) -> Dual {
+        let rhs = rhs.into();
+        Dual {
+            value: self.value * rhs.value,
+            derivative: self.derivative * rhs.value + self.value * rhs.derivative,
+        }
+    }
+}
+
+impl<T: Into<Dual>> Div<T> for Dual {
+    type Output = Dual;
+
+    fn div(self, rhs: T) -> Dual {
+        let rhs = rhs.into();
+        Dual {
+            value: self.value / rhs.value,
+            derivative: (self.derivative * rhs.value - self.value * rhs.derivative)
+                / (rhs.value * rhs.value),
+        }
+    }
+}
+
+impl Neg for Dual {
+    type Output = Dual;
+
+    fn neg(self) -> Dual {
+        Dual {
+            value: -self.value,
+            derivative: -self.derivative,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use common::{Greek, GreekMethod, Greeks, GreeksInput};
+
+use crate::dual::Dual;
+use crate::{norm_pdf, PricingContext};
+
+pub fn compute_greeks(input: &GreeksInput) -> Greeks {
+    let option = &input.option;
+    let (s, k, r, sigma, t) = (option.price, option.strike, option.rate, option.iv, option.time);
+    let method = input.method;
+
+    match method {
+        GreekMethod::Analytic => {
+            let context = PricingContext::new(s, r, sigma, t);
+            let vega = s * norm_pdf(context.d1(k)) * context.sqrt_t;
+            Greeks {
+                call: context.call_price(k),
+                put: context.put_price(k),
+                call_delta: Greek { value: context.call_delta(k), method },
+                put_delta: Greek { value: context.put_delta(k), method },
+                vega: Greek { value: vega, method },
+            }
+        }
+        GreekMethod::DualNumber => {
+            let constant = Dual::constant;
+            let spot = PricingContext::new(Dual::variable(s), constant(r), constant(sigma), constant(t));
+            let vol = PricingContext::new(constant(s), constant(r), Dual::variable(sigma), constant(t));
+            let (call, put) = (spot.call_price(constant(k)), spot.put_price(constant(k)));
+            let vega = vol.call_price(constant(k));
+            Greeks {
+                call: call.value,
+                put: put.value,
+                call_delta: Greek { value: call.derivative, method },
+                put_delta: Greek { value: put.derivative, method },
+                vega: Greek { value: vega.derivative, method },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::BlackScholesInput;
+
+    fn input(strike: f64, method: GreekMethod) -> GreeksInput {
+        GreeksInput {
+            option: BlackScholesInput {
+                price: 100.0,
+                strike,
+                iv: 0.25,
+                time: 0.75,
+                rate: 0.03,
+            },
+            method,
+        }
+    }
+
+    #[test]
+    fn dual_greeks_match_analytic_greeks() {
+        for strike in [70.0, 95.0, 100.0, 110.0, 150.0] {
+            let analytic = compute_greeks(&input(strike, GreekMethod::Analytic));
+            let dual = compute_greeks(&input(strike, GreekMethod::DualNumber));
+            // Both run the same pricer, so the prices agree exactly.
+            assert_eq!(dual.call.to_bits(), analytic.call.to_bits());
+            assert_eq!(dual.put.to_bits(), analytic.put.to_bits());
+            for (dual, analytic) in [
+                (dual.call_delta, analytic.call_delta),
+                (dual.put_delta, analytic.put_delta),
+                (dual.vega, analytic.vega),
+            ] {
+                assert!((dual.value - analytic.value).abs() < 1e-10, "{dual:?} vs {analytic:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn dual_delta_accounts_for_the_yield() {
+        let (s, k, r, q, sigma, t) = (100.0, 100.0, 0.03, 0.04, 0.25, 1.5);
+        let analytic = PricingContext::with_yield(s, r, q, sigma, t);
+        let constant = Dual::constant;
+        let dual = PricingContext::with_yield(Dual::variable(s), constant(r), constant(q), constant(sigma), constant(t));
+        assert!((dual.call_price(constant(k)).derivative - analytic.call_delta(k)).abs() < 1e-10);
+        assert!((dual.put_price(constant(k)).derivative - analytic.put_delta(k)).abs() < 1e-10);
+    }
+}
@@ -4,21 +4,32 @@
 // These two lines are necessary for the program to properly compile.
 //
 // Under the hood, we wrap your main function with some extra code so that it behaves properly
-// inside the zkVM.
-#![no_main]
+// inside the zkVM. Unit tests run on the host, where neither applies.
+#![cfg_attr(not(test), no_main)]
+#[cfg(not(test))]
 sp1_zkvm::entrypoint!(main);
 
+mod dual;
+mod greeks;
+mod parity;
+mod quanto;
+mod term_structure;
+mod theta;
+mod volatility;
+
 use std::f64::consts::PI;
-use serde::{Serialize, Deserialize};
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct BlackScholesInput {
-    pub price: f64,
-    pub strike: f64,
-    pub iv: f64,
-    pub time: f64,
-    pub rate: f64,
-}
+use sha2::{Digest, Sha256};
+use common::{
+    BlackScholesInput, BoundsPolicy, DaInclusion, EwmaInput, GarchInput, GreeksInput, Header,
+    ImpliedDividendInput, PricingRequest, QuantoInput, ThetaScheduleInput, SCHEMA_VERSION,
+};
+use dual::Real;
+use greeks::compute_greeks;
+use parity::implied_dividend_yield;
+use quanto::price_quanto;
+use term_structure::price_term_structure;
+use theta::theta_schedule;
+use volatility::{price_with_ewma, price_with_garch};
 
 fn norm_pdf(x: f64) -> f64 {
     (-x * x / 2.0).exp() / (2.0 * PI).sqrt()
@@ -31,37 +42,77 @@ fn norm_cdf(x: f64) -> f64 {
     if x > 0.0 { 1.0 - p } else { p }
 }
 
-fn d1(s: f64, k: f64, r: f64, sigma: f64, t: f64) -> f64 {
-    (s.ln() / k + (r + sigma * sigma / 2.0) * t) / (sigma * t.sqrt())
+/// Everything that depends only on (s, r, q, sigma, t), computed once and shared by
+/// every strike and side priced off it. Over [`dual::Dual`] numbers the same pricer yields
+/// sensitivities to whichever input was seeded.
+struct PricingContext<T = f64> {
+    s: T,
+    ln_s: T,
+    discount: T,
+    /// `e^{-qt}` for a continuous yield q; exactly 1 when there is none.
+    yield_discount: T,
+    forward: T,
+    sqrt_t: T,
+    total_vol: T,
+    drift: T,
 }
 
-fn d2(s: f64, k: f64, r: f64, sigma: f64, t: f64) -> f64 {
-    d1(s, k, r, sigma, t) - sigma * t.sqrt()
-}
+impl<T: Real> PricingContext<T> {
+    fn new(s: T, r: T, sigma: T, t: T) -> Self {
+        Self::with_yield(s, r, T::constant(0.0), sigma, t)
+    }
 
-fn call_price(s: f64, k: f64, r: f64, sigma: f64, t: f64) -> f64 {
-    let d1 = d1(s, k, r, sigma, t);
-    let d2 = d2(s, k, r, sigma, t);
-    s * norm_cdf(d1) - k * (-r * t).exp() * norm_cdf(d2)
-}
+    fn with_yield(s: T, r: T, q: T, sigma: T, t: T) -> Self {
+        let discount = (-r * t).exp();
+        let yield_discount = (-q * t).exp();
+        let sqrt_t = t.sqrt();
+        PricingContext {
+            s,
+            ln_s: s.ln(),
+            discount,
+            yield_discount,
+            forward: s * yield_discount / discount,
+            sqrt_t,
+            total_vol: sigma * sqrt_t,
+            drift: (r - q + sigma * sigma / T::constant(2.0)) * t,
+        }
+    }
 
-fn put_price(s: f64, k: f64, r: f64, sigma: f64, t: f64) -> f64 {
-    let d1 = d1(s, k, r, sigma, t);
-    let d2 = d2(s, k, r, sigma, t);
-    k * (-r * t).exp() * norm_cdf(-d2) - s * norm_cdf(-d1)
-}
+    fn d1(&self, k: T) -> T {
+        (self.ln_s - k.ln() + self.drift) / self.total_vol
+    }
 
-pub fn main() {
-    let option_input = sp1_zkvm::io::read::<BlackScholesInput>();
+    fn call_price(&self, k: T) -> T {
+        let d1 = self.d1(k);
+        let d2 = d1 - self.total_vol;
+        self.s * self.yield_discount * d1.norm_cdf() - k * self.discount * d2.norm_cdf()
+    }
+
+    fn put_price(&self, k: T) -> T {
+        let d1 = self.d1(k);
+        let d2 = d1 - self.total_vol;
+        k * self.discount * (-d2).norm_cdf() - self.s * self.yield_discount * (-d1).norm_cdf()
+    }
+
+    fn call_delta(&self, k: T) -> T {
+        self.yield_discount * self.d1(k).norm_cdf()
+    }
+
+    fn put_delta(&self, k: T) -> T {
+        -self.yield_discount * (-self.d1(k)).norm_cdf()
+    }
+}
 
+fn black_scholes(option_input: &BlackScholesInput) {
     let s = option_input.price; // Current stock price
     let k = option_input.strike; // Strike price
     let r = option_input.rate;  // Risk-free rate
     let sigma = option_input.iv; // Volatility
     let t = option_input.time;   // Time to expiration in years
 
-    let call = call_price(s, k, r, sigma, t);
-    let put = put_price(s, k, r, sigma, t);
+    let context = PricingContext::new(s, r, sigma, t);
+    let call = context.call_price(k);
+    let put = context.put_price(k);
 
     println!("Call option price: {:.4}", call);
     println!("Put option price: {:.4}", put);
@@ -72,3 +123,267 @@ pub fn main() {
     sp1_zkvm::io::commit_slice(&call_bytes);
     sp1_zkvm::io::commit_slice(&put_bytes);
 }
+
+fn implied_dividend(input: &ImpliedDividendInput) {
+    let implied = implied_dividend_yield(input);
+
+    println!("Implied dividend yield: {:?}", implied);
+
+    let input_bytes = bincode::serialize(input).unwrap();
+    let implied_bytes = bincode::serialize(&implied).unwrap();
+
+    sp1_zkvm::io::commit_slice(&input_bytes);
+    sp1_zkvm::io::commit_slice(&implied_bytes);
+}
+
+fn term_structure(options: &[BlackScholesInput]) {
+    let structure = price_term_structure(options);
+
+    println!("Term structure: {:?}", structure);
+
+    let structure_bytes = bincode::serialize(&structure).unwrap();
+
+    sp1_zkvm::io::commit_slice(&structure_bytes);
+
+    // Past the 4-byte `Ok` tag is the structure's own encoding, the canonical one. Its digest
+    // is committed last so that a contract can check the whole output set against 32 bytes.
+    if structure.is_ok() {
+        let outputs_digest: [u8; 32] = Sha256::digest(&structure_bytes[4..]).into();
+        sp1_zkvm::io::commit_slice(&outputs_digest);
+    }
+}
+
+fn quanto(input: &QuantoInput) {
+    let prices = price_quanto(input);
+
+    println!("Quanto prices: {:?}", prices);
+
+    let input_bytes = bincode::serialize(input).unwrap();
+    let prices_bytes = bincode::serialize(&prices).unwrap();
+
+    sp1_zkvm::io::commit_slice(&input_bytes);
+    sp1_zkvm::io::commit_slice(&prices_bytes);
+}
+
+fn greeks(input: &GreeksInput) {
+    let greeks = compute_greeks(input);
+
+    println!("Greeks: {:?}", greeks);
+
+    let input_bytes = bincode::serialize(input).unwrap();
+    let greeks_bytes = bincode::serialize(&greeks).unwrap();
+
+    sp1_zkvm::io::commit_slice(&input_bytes);
+    sp1_zkvm::io::commit_slice(&greeks_bytes);
+}
+
+fn ewma(input: &EwmaInput, policy: &BoundsPolicy) {
+    let estimate = price_with_ewma(input, policy);
+
+    println!("EWMA estimate: {:?}", estimate);
+
+    let series_hash: [u8; 32] = Sha256::digest(bincode::serialize(&input.series).unwrap()).into();
+    let lambda_bytes = bincode::serialize(&input.lambda).unwrap();
+    let estimate_bytes = bincode::serialize(&estimate).unwrap();
+
+    sp1_zkvm::io::commit_slice(&series_hash);
+    sp1_zkvm::io::commit_slice(&lambda_bytes);
+    sp1_zkvm::io::commit_slice(&estimate_bytes);
+}
+
+fn garch(input: &GarchInput, policy: &BoundsPolicy) {
+    let estimate = price_with_garch(input, policy);
+
+    println!("GARCH(1,1) estimate: {:?}", estimate);
+
+    let params_hash: [u8; 32] = Sha256::digest(bincode::serialize(&input.params).unwrap()).into();
+    let series_hash: [u8; 32] = Sha256::digest(bincode::serialize(&input.series).unwrap()).into();
+    let horizon_bytes = bincode::serialize(&input.horizon).unwrap();
+    let estimate_bytes = bincode::serialize(&estimate).unwrap();
+
+    sp1_zkvm::io::commit_slice(&params_hash);
+    sp1_zkvm::io::commit_slice(&series_hash);
+    sp1_zkvm::io::commit_slice(&horizon_bytes);
+    sp1_zkvm::io::commit_slice(&estimate_bytes);
+}
+
+fn theta(input: &ThetaScheduleInput) {
+    let schedule = theta_schedule(input);
+
+    println!("Theta schedule: {:?}", schedule);
+
+    let schedule_bytes = bincode::serialize(&schedule).unwrap();
+
+    sp1_zkvm::io::commit_slice(&schedule_bytes);
+}
+
+pub fn main() {
+    // The raw request bytes are exactly the blob the script submitted to Avail.
+    let request_bytes = sp1_zkvm::io::read_vec();
+    let request = bincode::deserialize::<PricingRequest>(&request_bytes).unwrap();
+    let policy = sp1_zkvm::io::read::<BoundsPolicy>();
+    let inclusion = sp1_zkvm::io::read::<DaInclusion>();
+
+    let header = Header {
+        schema_version: SCHEMA_VERSION,
+        input_hash: Sha256::digest(&request_bytes).into(),
+        inclusion,
+        policy_hash: Sha256::digest(bincode::serialize(&policy).unwrap()).into(),
+        check: policy.validate(&request),
+    };
+
+    println!("Bounds policy check: {:?}", header.check);
+
+    let header_bytes = bincode::serialize(&header).unwrap();
+
+    sp1_zkvm::io::commit_slice(&header_bytes);
+
+    if header.check.is_err() {
+        return;
+    }
+
+    match &request {
+        PricingRequest::BlackScholes(option_input) => black_scholes(option_input),
+        PricingRequest::ImpliedDividend(input) => implied_dividend(input),
+        PricingRequest::TermStructure(options) => term_structure(options),
+        PricingRequest::Quanto(input) => quanto(input),
+        PricingRequest::Greeks(input) => greeks(input),
+        PricingRequest::Ewma(input) => ewma(input, &policy),
+        PricingRequest::Garch(input) => garch(input, &policy),
+        PricingRequest::ThetaSchedule(input) => theta(input),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(time: f64) -> BlackScholesInput {
+        BlackScholesInput {
+            price: 100.0,
+            strike: 105.0,
+            iv: 0.2,
+            time,
+            rate: 0.05,
+        }
+    }
+
+    /// The free functions [`PricingContext`] replaced, verbatim but for visibility.
+    mod replaced {
+        use super::super::norm_cdf;
+
+        fn d1(s: f64, k: f64, r: f64, sigma: f64, t: f64) -> f64 {
+            (s.ln() - k.ln() + (r + sigma * sigma / 2.0) * t) / (sigma * t.sqrt())
+        }
+
+        fn d2(s: f64, k: f64, r: f64, sigma: f64, t: f64) -> f64 {
+            d1(s, k, r, sigma, t) - sigma * t.sqrt()
+        }
+
+        pub fn call_price(s: f64, k: f64, r: f64, sigma: f64, t: f64) -> f64 {
+            let d1 = d1(s, k, r, sigma, t);
+            let d2 = d2(s, k, r, sigma, t);
+            s * norm_cdf(d1) - k * (-r * t).exp() * norm_cdf(d2)
+        }
+
+        pub fn put_price(s: f64, k: f64, r: f64, sigma: f64, t: f64) -> f64 {
+            let d1 = d1(s, k, r, sigma, t);
+            let d2 = d2(s, k, r, sigma, t);
+            k * (-r * t).exp() * norm_cdf(-d2) - s * norm_cdf(-d1)
+        }
+    }
+
+    #[test]
+    fn context_prices_match_the_replaced_pricer_bitwise() {
+        for &(s, r, sigma, t) in &[(100.0, 0.05, 0.2, 1.0), (42.5, 0.0, 0.65, 0.25), (2500.0, 0.12, 0.05, 3.0)] {
+            let context = PricingContext::new(s, r, sigma, t);
+            for strike in 1..=100 {
+                let k = s * (0.5 + strike as f64 / 100.0);
+                assert_eq!(context.call_price(k).to_bits(), replaced::call_price(s, k, r, sigma, t).to_bits());
+                assert_eq!(context.put_price(k).to_bits(), replaced::put_price(s, k, r, sigma, t).to_bits());
+            }
+        }
+    }
+
+    #[test]
+    fn prices_match_textbook_values() {
+        // Hull's at-the-money example: s = k = 100, r = 5%, sigma = 20%, one year.
+        let context = PricingContext::new(100.0, 0.05, 0.2, 1.0);
+        assert!((context.call_price(100.0) - 10.4506).abs() < 1e-3);
+        assert!((context.put_price(100.0) - 5.5735).abs() < 1e-3);
+    }
+
+    #[test]
+    fn prices_satisfy_put_call_parity() {
+        let context = PricingContext::new(100.0, 0.05, 0.2, 1.0);
+        for &k in &[60.0, 95.0, 105.0, 180.0] {
+            let parity = context.call_price(k) - context.put_price(k) - (100.0 - k * (-0.05f64).exp());
+            assert!(parity.abs() < 1e-10, "parity residual {parity} at k = {k}");
+        }
+    }
+
+    #[test]
+    fn oversized_term_structures_decode_and_are_rejected_by_pricing() {
+        let options = (1..=common::MAX_TERM_POINTS + 1).map(|i| option(i as f64)).collect();
+        let bytes = bincode::serialize(&PricingRequest::TermStructure(options)).unwrap();
+        match bincode::deserialize::<PricingRequest>(&bytes).unwrap() {
+            PricingRequest::TermStructure(options) => assert!(matches!(
+                price_term_structure(&options),
+                Err(common::TermStructureError::TooLong)
+            )),
+            request => panic!("decoded {:?}", request),
+        }
+    }
+
+    #[test]
+    fn oversized_price_series_decode_and_are_rejected_by_pricing() {
+        let request = PricingRequest::Ewma(EwmaInput {
+            price: 100.0,
+            strike: 105.0,
+            time: 1.0,
+            rate: 0.05,
+            series: common::PriceSeries {
+                closes: (0..=common::MAX_SERIES_LEN).map(|i| 100.0 + (i % 2) as f64).collect(),
+                periods_per_year: 252.0,
+            },
+            lambda: 0.94,
+        });
+        let bytes = bincode::serialize(&request).unwrap();
+        match bincode::deserialize::<PricingRequest>(&bytes).unwrap() {
+            PricingRequest::Ewma(input) => assert_eq!(
+                volatility::ewma_vol(&input.series, input.lambda),
+                Err(common::SeriesError::TooLong)
+            ),
+            request => panic!("decoded {:?}", request),
+        }
+    }
+
+    #[test]
+    fn oversized_theta_schedules_decode_and_are_rejected_by_pricing() {
+        let request = PricingRequest::ThetaSchedule(ThetaScheduleInput {
+            option: option(1.0),
+            offsets_days: (0..=common::MAX_THETA_OFFSETS as u32).collect(),
+        });
+        let bytes = bincode::serialize(&request).unwrap();
+        match bincode::deserialize::<PricingRequest>(&bytes).unwrap() {
+            PricingRequest::ThetaSchedule(input) => assert_eq!(
+                theta_schedule(&input).map(|schedule| schedule.points.len()),
+                Err(common::ThetaError::TooManyOffsets)
+            ),
+            request => panic!("decoded {:?}", request),
+        }
+    }
+
+    #[test]
+    fn negative_rates_keep_parity_and_no_arbitrage_bounds() {
+        let (s, r, t) = (100.0, -0.01, 2.0);
+        let context = PricingContext::new(s, r, 0.2, t);
+        for &k in &[50.0, 100.0, 150.0] {
+            let (call, put) = (context.call_price(k), context.put_price(k));
+            let discounted_strike = k * (-r * t).exp();
+            assert!((call - put - (s - discounted_strike)).abs() < 1e-10);
+            assert!(call >= (s - discounted_strike).max(0.0) && call <= s);
+            assert!(put >= (discounted_strike - s).max(0.0) && put <= discounted_strike);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use common::{ImpliedDividend, ImpliedDividendError, ImpliedDividendInput};
+
+/// Solves put-call parity for the continuous dividend yield implied by the quotes.
+pub fn implied_dividend_yield(
+    input: &ImpliedDividendInput,
+) -> Result<ImpliedDividend, ImpliedDividendError> {
+    let fields = [input.call, input.put, input.price, input.strike, input.time, input.rate];
+    if fields.iter().any(|field| !field.is_finite())
+        || input.call < 0.0
+        || input.put < 0.0
+        || input.price <= 0.0
+        || input.strike <= 0.0
+        || input.time <= 0.0
+    {
+        return Err(ImpliedDividendError::InvalidInput);
+    }
+
+    let t = input.time;
+    let discounted_strike = input.strike * (-input.rate * t).exp();
+    // s·e^{-qt}, the spot net of the carry the quotes imply.
+    let discounted_spot = input.call - input.put + discounted_strike;
+    if input.put > discounted_strike || discounted_spot <= 0.0 {
+        return Err(ImpliedDividendError::ArbitrageBoundViolated);
+    }
+
+    let dividend_yield = -(discounted_spot / input.price).ln() / t;
+    let residual = input.call
+        - input.put
+        - (input.price * (-dividend_yield * t).exp() - discounted_strike);
+
+    Ok(ImpliedDividend {
+        dividend_yield,
+        residual,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PricingContext;
+
+    fn quotes(s: f64, k: f64, r: f64, q: f64, sigma: f64, t: f64) -> ImpliedDividendInput {
+        let context = PricingContext::with_yield(s, r, q, sigma, t);
+        ImpliedDividendInput {
+            call: context.call_price(k),
+            put: context.put_price(k),
+            price: s,
+            strike: k,
+            time: t,
+            rate: r,
+        }
+    }
+
+    #[test]
+    fn recovers_the_yield_the_quotes_were_priced_with() {
+        for &(k, q, t) in &[(100.0, 0.02, 1.0), (80.0, 0.0, 0.5), (125.0, 0.045, 2.0), (100.0, -0.01, 0.25)] {
+            let implied = implied_dividend_yield(&quotes(100.0, k, 0.05, q, 0.25, t)).unwrap();
+            assert!((implied.dividend_yield - q).abs() < 1e-10, "recovered {} for q = {q}", implied.dividend_yield);
+            assert!(implied.residual.abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn put_above_discounted_strike_is_rejected() {
+        let mut input = quotes(100.0, 100.0, 0.05, 0.02, 0.2, 1.0);
+        input.put = 100.0 * (-0.05f64).exp() + 0.01;
+        assert!(matches!(implied_dividend_yield(&input), Err(ImpliedDividendError::ArbitrageBoundViolated)));
+    }
+
+    #[test]
+    fn non_positive_implied_spot_is_rejected() {
+        let mut input = quotes(100.0, 100.0, 0.05, 0.02, 0.2, 1.0);
+        // A zero call with the put at the discounted strike implies s·e^{-qt} = 0.
+        input.call = 0.0;
+        input.put = 100.0 * (-0.05f64).exp();
+        assert!(matches!(implied_dividend_yield(&input), Err(ImpliedDividendError::ArbitrageBoundViolated)));
+    }
+
+    #[test]
+    fn invalid_fields_are_rejected() {
+        let valid = quotes(100.0, 100.0, 0.05, 0.02, 0.2, 1.0);
+        let mut nan_rate = valid.clone();
+        nan_rate.rate = f64::NAN;
+        let mut zero_time = valid.clone();
+        zero_time.time = 0.0;
+        let mut negative_call = valid;
+        negative_call.call = -1.0;
+        for input in [nan_rate, zero_time, negative_call] {
+            assert!(matches!(implied_dividend_yield(&input), Err(ImpliedDividendError::InvalidInput)));
+        }
+    }
+
+    #[test]
+    fn recovers_the_yield_under_a_negative_rate() {
+        for q in [-0.02, 0.0, 0.03] {
+            let implied = implied_dividend_yield(&quotes(100.0, 100.0, -0.01, q, 0.2, 1.0)).unwrap();
+            assert!((implied.dividend_yield - q).abs() < 1e-10, "recovered {} for q = {q}", implied.dividend_yield);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use common::{QuantoError, QuantoInput, QuantoPrices};
+
+use crate::PricingContext;
+
+/// Black-Scholes with the foreign drift adjusted by `-rho·sigma_S·sigma_FX` and discounting
+/// at the domestic rate. With no correlation or no FX vol this is exactly the
+/// dividend-adjusted price with yield `r_d - r_f`.
+pub fn price_quanto(input: &QuantoInput) -> Result<QuantoPrices, QuantoError> {
+    let fields = [
+        input.price,
+        input.strike,
+        input.iv,
+        input.time,
+        input.domestic_rate,
+        input.foreign_rate,
+        input.fx_vol,
+        input.correlation,
+        input.conversion_rate,
+    ];
+    if fields.iter().any(|field| !field.is_finite())
+        || input.price <= 0.0
+        || input.strike <= 0.0
+        || input.iv <= 0.0
+        || input.time <= 0.0
+        || input.fx_vol < 0.0
+        || input.conversion_rate <= 0.0
+    {
+        return Err(QuantoError::InvalidInput);
+    }
+    if !(-1.0..=1.0).contains(&input.correlation) {
+        return Err(QuantoError::CorrelationOutOfRange);
+    }
+
+    let quanto_drift = input.foreign_rate - input.correlation * input.iv * input.fx_vol;
+    let context = PricingContext::with_yield(
+        input.price,
+        input.domestic_rate,
+        input.domestic_rate - quanto_drift,
+        input.iv,
+        input.time,
+    );
+    let k = input.strike;
+    let x = input.conversion_rate;
+
+    Ok(QuantoPrices {
+        call: x * context.call_price(k),
+        put: x * context.put_price(k),
+        call_delta: x * context.call_delta(k),
+        put_delta: x * context.put_delta(k),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(correlation: f64, fx_vol: f64) -> QuantoInput {
+        QuantoInput {
+            price: 100.0,
+            strike: 105.0,
+            iv: 0.25,
+            time: 1.0,
+            domestic_rate: 0.05,
+            foreign_rate: 0.02,
+            fx_vol,
+            correlation,
+            conversion_rate: 1.0,
+        }
+    }
+
+    fn dividend_adjusted(input: &QuantoInput) -> (f64, f64) {
+        let context = PricingContext::with_yield(
+            input.price,
+            input.domestic_rate,
+            input.domestic_rate - input.foreign_rate,
+            input.iv,
+            input.time,
+        );
+        (context.call_price(input.strike), context.put_price(input.strike))
+    }
+
+    #[test]
+    fn no_correlation_or_no_fx_vol_reduces_to_the_dividend_adjusted_price() {
+        for input in [input(0.0, 0.1), input(0.6, 0.0)] {
+            let prices = price_quanto(&input).unwrap();
+            let (call, put) = dividend_adjusted(&input);
+            assert_eq!(prices.call.to_bits(), call.to_bits());
+            assert_eq!(prices.put.to_bits(), put.to_bits());
+        }
+    }
+
+    #[test]
+    fn adjustment_sign_follows_correlation() {
+        let uncorrelated = price_quanto(&input(0.0, 0.1)).unwrap();
+        let positive = price_quanto(&input(0.5, 0.1)).unwrap();
+        let negative = price_quanto(&input(-0.5, 0.1)).unwrap();
+        // Positive correlation lowers the quanto drift, cheapening the call.
+        assert!(positive.call < uncorrelated.call && uncorrelated.call < negative.call);
+        assert!(positive.put > uncorrelated.put && uncorrelated.put > negative.put);
+    }
+
+    #[test]
+    fn prices_scale_with_the_conversion_rate() {
+        let base = price_quanto(&input(0.3, 0.1)).unwrap();
+        let mut converted = input(0.3, 0.1);
+        converted.conversion_rate = 2.0;
+        let converted = price_quanto(&converted).unwrap();
+        assert_eq!(converted.call, 2.0 * base.call);
+        assert_eq!(converted.put_delta, 2.0 * base.put_delta);
+    }
+
+    #[test]
+    fn invalid_inputs_are_rejected() {
+        assert!(matches!(price_quanto(&input(1.01, 0.1)), Err(QuantoError::CorrelationOutOfRange)));
+        assert!(matches!(price_quanto(&input(0.0, -0.1)), Err(QuantoError::InvalidInput)));
+        assert!(matches!(price_quanto(&input(f64::NAN, 0.1)), Err(QuantoError::InvalidInput)));
+    }
+
+    #[test]
+    fn negative_domestic_rate_reduces_to_the_dividend_adjusted_price() {
+        let mut input = input(0.0, 0.1);
+        input.domestic_rate = -0.01;
+        let prices = price_quanto(&input).unwrap();
+        let (call, put) = dividend_adjusted(&input);
+        assert_eq!(prices.call.to_bits(), call.to_bits());
+        assert_eq!(prices.put.to_bits(), put.to_bits());
+        assert!(prices.call > 0.0 && prices.put > 0.0);
+    }
+}
@@ -0,0 +1,188 @@
+use common::{
+    BlackScholesInput, TermPoint, TermStructure, TermStructureError, MAX_TERM_POINTS,
+};
+
+use crate::PricingContext;
+
+/// Prices the same strike across several expiries, each with its own vol and rate pillar,
+/// and checks that call prices never decrease with tenor.
+pub fn price_term_structure(
+    options: &[BlackScholesInput],
+) -> Result<TermStructure, TermStructureError> {
+    let (strike, spot) = match options.first() {
+        Some(option) => (option.strike, option.price),
+        None => return Err(TermStructureError::Empty),
+    };
+    if options.len() > MAX_TERM_POINTS {
+        return Err(TermStructureError::TooLong);
+    }
+    if options.iter().any(|option| option.strike != strike) {
+        return Err(TermStructureError::MismatchedStrike);
+    }
+    if options.iter().any(|option| option.price != spot) {
+        return Err(TermStructureError::MismatchedSpot);
+    }
+
+    let mut prices = Vec::with_capacity(options.len());
+    // Each point with `r·t`, the exponent of its strike's discount factor, which only the
+    // arbitrage check needs.
+    let mut pillars = Vec::with_capacity(options.len());
+    for option in options {
+        let context = PricingContext::new(option.price, option.rate, option.iv, option.time);
+        let call = context.call_price(strike);
+        prices.push((call, context.put_price(strike)));
+        let point = TermPoint {
+            time: option.time,
+            call,
+            forward: context.forward,
+        };
+        pillars.push((point, option.rate * option.time));
+    }
+    pillars.sort_by(|(a, _), (b, _)| a.time.total_cmp(&b.time));
+    if pillars.windows(2).any(|pair| pair[0].0.time == pair[1].0.time) {
+        return Err(TermStructureError::DuplicateTenor);
+    }
+
+    // A longer-dated call is worth at least a shorter-dated one when its strike is discounted
+    // by at least as much, `k·e^{-r_long·t_long} <= k·e^{-r_short·t_short}`. Other pairs carry
+    // no bound, and skipping them breaks transitivity, so every ordered pair is compared.
+    let calendar_arbitrage = pillars.iter().enumerate().any(|(i, (short, short_exponent))| {
+        pillars[i + 1..]
+            .iter()
+            .any(|(long, long_exponent)| long_exponent >= short_exponent && long.call < short.call)
+    });
+    let points = pillars.into_iter().map(|(point, _)| point).collect();
+
+    Ok(TermStructure {
+        prices,
+        points,
+        calendar_arbitrage,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(time: f64, iv: f64, rate: f64) -> BlackScholesInput {
+        BlackScholesInput {
+            price: 100.0,
+            strike: 105.0,
+            iv,
+            time,
+            rate,
+        }
+    }
+
+    #[test]
+    fn flat_vol_has_no_calendar_arbitrage() {
+        let options = [option(1.0, 0.2, 0.05), option(0.25, 0.2, 0.05), option(2.0, 0.2, 0.05)];
+        let structure = price_term_structure(&options).unwrap();
+        assert!(!structure.calendar_arbitrage);
+        let tenors: Vec<f64> = structure.points.iter().map(|point| point.time).collect();
+        assert_eq!(tenors, [0.25, 1.0, 2.0]);
+        // Prices stay in input order while points are sorted by tenor.
+        assert_eq!(structure.prices[0].0, structure.points[1].call);
+        assert!((structure.points[2].forward - 100.0 * (0.1f64).exp()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn collapsing_vol_trips_calendar_arbitrage() {
+        // The same fixture as script/requests/term-structure-calendar-arbitrage.json.
+        let options = [option(0.5, 0.4, 0.05), option(1.0, 0.15, 0.05)];
+        let structure = price_term_structure(&options).unwrap();
+        assert!(structure.points[1].call < structure.points[0].call);
+        assert!(structure.calendar_arbitrage);
+    }
+
+    #[test]
+    fn malformed_lists_are_rejected() {
+        assert!(matches!(price_term_structure(&[]), Err(TermStructureError::Empty)));
+
+        let mut mismatched = [option(0.5, 0.2, 0.05), option(1.0, 0.2, 0.05)];
+        mismatched[1].strike = 110.0;
+        assert!(matches!(
+            price_term_structure(&mismatched),
+            Err(TermStructureError::MismatchedStrike)
+        ));
+
+        let mut other_spot = [option(0.5, 0.2, 0.05), option(1.0, 0.2, 0.05)];
+        other_spot[1].price = 101.0;
+        assert!(matches!(
+            price_term_structure(&other_spot),
+            Err(TermStructureError::MismatchedSpot)
+        ));
+
+        // Equal tenors are rejected wherever they sit in the input, even with equal prices.
+        let duplicated = [option(1.0, 0.3, 0.05), option(0.5, 0.2, 0.05), option(1.0, 0.2, 0.01)];
+        assert!(matches!(
+            price_term_structure(&duplicated),
+            Err(TermStructureError::DuplicateTenor)
+        ));
+        assert!(matches!(
+            price_term_structure(&vec![option(1.0, 0.2, 0.05); 2]),
+            Err(TermStructureError::DuplicateTenor)
+        ));
+
+        let too_long: Vec<BlackScholesInput> =
+            (1..=MAX_TERM_POINTS + 1).map(|i| option(i as f64 / 12.0, 0.2, 0.05)).collect();
+        assert!(matches!(price_term_structure(&too_long), Err(TermStructureError::TooLong)));
+        assert!(price_term_structure(&too_long[..MAX_TERM_POINTS]).is_ok());
+    }
+
+    /// Deep in the money with almost no vol, the call is close to `s - k·e^{-rt}`, so it
+    /// tracks the discounted strike across tenors.
+    fn deep(time: f64, iv: f64, rate: f64) -> BlackScholesInput {
+        BlackScholesInput {
+            price: 100.0,
+            strike: 50.0,
+            iv,
+            time,
+            rate,
+        }
+    }
+
+    #[test]
+    fn cheaper_calls_with_less_discounted_strikes_are_not_flagged() {
+        // r·t falls with tenor under a flat negative rate...
+        let negative = price_term_structure(&[deep(1.0, 0.01, -0.01), deep(2.0, 0.01, -0.01)]).unwrap();
+        assert!(negative.points[1].call < negative.points[0].call);
+        assert!(!negative.calendar_arbitrage);
+
+        // ...and under a positive curve falling fast enough: 0.05 at one year against 0.01 at
+        // two years discounts the longer-dated strike by less.
+        let falling = price_term_structure(&[deep(1.0, 0.01, 0.05), deep(2.0, 0.01, 0.01)]).unwrap();
+        assert!(falling.points[1].call < falling.points[0].call);
+        assert!(!falling.calendar_arbitrage);
+    }
+
+    #[test]
+    fn cheaper_calls_with_more_discounted_strikes_are_flagged_at_any_sign() {
+        // Both rates are negative, but -0.001 over two years discounts the strike by more than
+        // -0.01 over one, so the collapsing vol is an arbitrage.
+        let options = [option(1.0, 0.4, -0.01), option(2.0, 0.1, -0.001)];
+        let structure = price_term_structure(&options).unwrap();
+        assert!(structure.points[1].call < structure.points[0].call);
+        assert!(structure.calendar_arbitrage);
+    }
+
+    #[test]
+    fn pairs_are_compared_past_a_skipped_neighbour() {
+        // The middle tenor's strike is less discounted than the first's, so that pair carries
+        // no bound; the first and last still do, and the last is cheaper.
+        let options = [deep(1.0, 0.6, 0.05), deep(2.0, 0.01, 0.01), deep(3.0, 0.01, 0.02)];
+        let structure = price_term_structure(&options).unwrap();
+        assert!(structure.points[2].call > structure.points[1].call);
+        assert!(structure.points[2].call < structure.points[0].call);
+        assert!(structure.calendar_arbitrage);
+
+        let mixed = [
+            deep(1.0, 0.01, -0.01),
+            deep(2.0, 0.01, -0.01),
+            deep(3.0, 0.6, 0.05),
+            deep(4.0, 0.01, 0.05),
+        ];
+        assert!(price_term_structure(&mixed).unwrap().calendar_arbitrage);
+        assert!(!price_term_structure(&mixed[..3]).unwrap().calendar_arbitrage);
+    }
+}
@@ -0,0 +1,94 @@
+use common::{ThetaError, ThetaPoint, ThetaSchedule, ThetaScheduleInput, MAX_THETA_OFFSETS};
+
+use crate::PricingContext;
+
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Re-prices the option at each forward offset. Offsets at or beyond expiry are clamped to
+/// expiry, where the value is intrinsic.
+pub fn theta_schedule(input: &ThetaScheduleInput) -> Result<ThetaSchedule, ThetaError> {
+    if input.offsets_days.len() > MAX_THETA_OFFSETS {
+        return Err(ThetaError::TooManyOffsets);
+    }
+    let option = &input.option;
+    let (s, k) = (option.price, option.strike);
+
+    let context = PricingContext::new(s, option.rate, option.iv, option.time);
+    let points = input
+        .offsets_days
+        .iter()
+        .map(|&offset_days| {
+            let remaining = option.time - f64::from(offset_days) / DAYS_PER_YEAR;
+            let (call, put) = if remaining > 0.0 {
+                let context = PricingContext::new(s, option.rate, option.iv, remaining);
+                (context.call_price(k), context.put_price(k))
+            } else {
+                ((s - k).max(0.0), (k - s).max(0.0))
+            };
+            ThetaPoint {
+                offset_days,
+                call,
+                put,
+            }
+        })
+        .collect();
+
+    Ok(ThetaSchedule {
+        call: context.call_price(k),
+        put: context.put_price(k),
+        points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::BlackScholesInput;
+
+    fn input(time: f64, rate: f64, offsets_days: Vec<u32>) -> ThetaScheduleInput {
+        ThetaScheduleInput {
+            option: BlackScholesInput {
+                price: 100.0,
+                strike: 105.0,
+                iv: 0.2,
+                time,
+                rate,
+            },
+            offsets_days,
+        }
+    }
+
+    #[test]
+    fn values_decay_to_intrinsic_with_zero_rates() {
+        // 73 days is exactly 0.2 years, so the last offset lands on expiry.
+        let schedule = theta_schedule(&input(0.2, 0.0, vec![1, 7, 30, 60, 73])).unwrap();
+        let mut previous = (schedule.call, schedule.put);
+        for point in &schedule.points {
+            assert!(point.call <= previous.0 && point.put <= previous.1, "{point:?}");
+            previous = (point.call, point.put);
+        }
+        let expiry = schedule.points.last().unwrap();
+        assert_eq!((expiry.call, expiry.put), (0.0, 5.0));
+    }
+
+    #[test]
+    fn offsets_past_expiry_are_clamped_to_intrinsic() {
+        // 0.25 years is 91.25 days, so 92 days onwards is past expiry.
+        let schedule = theta_schedule(&input(0.25, 0.05, vec![30, 92, 365, u32::MAX])).unwrap();
+        assert_eq!(schedule.points.len(), 4);
+        for point in &schedule.points[1..] {
+            assert_eq!((point.call, point.put), (0.0, 5.0));
+        }
+        assert!(schedule.points[0].put > 0.0 && schedule.points[0].call > 0.0);
+    }
+
+    #[test]
+    fn offsets_are_capped() {
+        let offsets: Vec<u32> = (1..=MAX_THETA_OFFSETS as u32 + 1).collect();
+        assert!(theta_schedule(&input(0.25, 0.05, offsets[..MAX_THETA_OFFSETS].to_vec())).is_ok());
+        assert_eq!(
+            theta_schedule(&input(0.25, 0.05, offsets)).map(|schedule| schedule.points.len()),
+            Err(ThetaError::TooManyOffsets)
+        );
+    }
+}
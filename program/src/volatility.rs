@@ -0,0 +1,346 @@
+use common::{
+    BoundsPolicy, EwmaInput, GarchInput, GarchParams, PriceSeries, SeriesError, VolEstimate,
+    MAX_SERIES_LEN, MIN_SERIES_LEN,
+};
+
+use crate::PricingContext;
+
+fn validate_series(series: &PriceSeries) -> Result<(), SeriesError> {
+    if series.closes.len() < MIN_SERIES_LEN {
+        return Err(SeriesError::TooShort);
+    }
+    if series.closes.len() > MAX_SERIES_LEN {
+        return Err(SeriesError::TooLong);
+    }
+    if series
+        .closes
+        .iter()
+        .any(|close| !(close.is_finite() && *close > 0.0))
+    {
+        return Err(SeriesError::NonPositivePrice);
+    }
+    if !(series.periods_per_year.is_finite() && series.periods_per_year > 0.0) {
+        return Err(SeriesError::InvalidPeriodsPerYear);
+    }
+    Ok(())
+}
+
+fn log_returns(series: &PriceSeries) -> Vec<f64> {
+    series
+        .closes
+        .windows(2)
+        .map(|pair| (pair[1] / pair[0]).ln())
+        .collect()
+}
+
+/// EWMA variance of the log returns, weighting the latest return by 1 and each older one by a
+/// further factor of `lambda`. The weighted mean is removed, so `lambda = 1` is exactly the
+/// equally weighted (population) standard deviation.
+pub fn ewma_vol(series: &PriceSeries, lambda: f64) -> Result<f64, SeriesError> {
+    validate_series(series)?;
+    if !(lambda > 0.0 && lambda <= 1.0) {
+        return Err(SeriesError::InvalidParameter);
+    }
+
+    let returns = log_returns(series);
+    let mut weights = vec![0.0; returns.len()];
+    let mut weight = 1.0;
+    for slot in weights.iter_mut().rev() {
+        *slot = weight;
+        weight *= lambda;
+    }
+    let total_weight: f64 = weights.iter().sum();
+
+    let mean = returns
+        .iter()
+        .zip(&weights)
+        .map(|(r, w)| w * r)
+        .sum::<f64>()
+        / total_weight;
+    let variance = returns
+        .iter()
+        .zip(&weights)
+        .map(|(r, w)| w * (r - mean) * (r - mean))
+        .sum::<f64>()
+        / total_weight;
+
+    Ok((variance * series.periods_per_year).sqrt())
+}
+
+/// Runs the variance recursion over the series' log returns, starting from their mean square,
+/// and returns the annualized `horizon`-step-ahead volatility forecast
+/// `V + (alpha + beta)^{horizon - 1}·(h_{n+1} - V)` with `V = omega / (1 - alpha - beta)`.
+pub fn garch_vol(
+    series: &PriceSeries,
+    params: &GarchParams,
+    horizon: u32,
+) -> Result<f64, SeriesError> {
+    validate_series(series)?;
+    let GarchParams { omega, alpha, beta } = *params;
+    if !(omega > 0.0 && alpha >= 0.0 && beta >= 0.0 && omega.is_finite()) || horizon == 0 {
+        return Err(SeriesError::InvalidParameter);
+    }
+    let persistence = alpha + beta;
+    // Both terms passed `>= 0.0` above, so the sum is not NaN.
+    if persistence >= 1.0 {
+        return Err(SeriesError::NonStationary);
+    }
+
+    let returns = log_returns(series);
+    let mut variance = returns.iter().map(|r| r * r).sum::<f64>() / returns.len() as f64;
+    for r in &returns {
+        variance = omega + alpha * r * r + beta * variance;
+    }
+
+    let unconditional = omega / (1.0 - persistence);
+    let forecast =
+        unconditional + persistence.powf(f64::from(horizon - 1)) * (variance - unconditional);
+
+    Ok((forecast * series.periods_per_year).sqrt())
+}
+
+fn price_with_vol(price: f64, strike: f64, rate: f64, vol: f64, time: f64) -> VolEstimate {
+    let context = PricingContext::new(price, rate, vol, time);
+    VolEstimate {
+        vol,
+        call: context.call_price(strike),
+        put: context.put_price(strike),
+    }
+}
+
+/// Rejects a degenerate or out-of-policy estimate before anything is priced with it.
+fn check_vol(vol: f64, policy: &BoundsPolicy) -> Result<f64, SeriesError> {
+    if !(vol.is_finite() && vol > 0.0) {
+        return Err(SeriesError::DegenerateVolatility);
+    }
+    policy
+        .check_estimated_vol(vol)
+        .map_err(SeriesError::OutOfPolicy)?;
+    Ok(vol)
+}
+
+pub fn price_with_ewma(
+    input: &EwmaInput,
+    policy: &BoundsPolicy,
+) -> Result<VolEstimate, SeriesError> {
+    let vol = check_vol(ewma_vol(&input.series, input.lambda)?, policy)?;
+    Ok(price_with_vol(
+        input.price,
+        input.strike,
+        input.rate,
+        vol,
+        input.time,
+    ))
+}
+
+pub fn price_with_garch(
+    input: &GarchInput,
+    policy: &BoundsPolicy,
+) -> Result<VolEstimate, SeriesError> {
+    let vol = check_vol(garch_vol(&input.series, &input.params, input.horizon)?, policy)?;
+    Ok(price_with_vol(
+        input.price,
+        input.strike,
+        input.rate,
+        vol,
+        input.time,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::BoundViolation;
+
+    /// Daily closes shared with script/requests/ewma.json.
+    const CLOSES: [f64; 12] = [
+        100.0, 101.2, 100.5, 99.8, 101.9, 102.4, 101.1, 103.0, 102.2, 104.1, 103.5, 105.0,
+    ];
+
+    fn series(closes: &[f64]) -> PriceSeries {
+        PriceSeries {
+            closes: closes.to_vec(),
+            periods_per_year: 252.0,
+        }
+    }
+
+    fn policy() -> BoundsPolicy {
+        BoundsPolicy {
+            min_price: 0.0001,
+            max_price: 1e9,
+            min_strike: 0.0001,
+            max_strike: 1e9,
+            min_iv: 0.01,
+            max_iv: 5.0,
+            min_time: 0.0001,
+            max_time: 5.0,
+            max_abs_rate: 0.25,
+        }
+    }
+
+    fn ewma_input(closes: &[f64], lambda: f64) -> EwmaInput {
+        EwmaInput {
+            price: 100.0,
+            strike: 105.0,
+            time: 1.0,
+            rate: 0.05,
+            series: series(closes),
+            lambda,
+        }
+    }
+
+    /// Closes alternating between `low` and `high`, whose returns are all `±ln(high / low)`.
+    fn alternating(low: f64, high: f64, len: usize) -> Vec<f64> {
+        (0..len).map(|i| if i % 2 == 0 { low } else { high }).collect()
+    }
+
+    /// EWMA variance as `E_w[r²] - E_w[r]²` with the weights written out as powers.
+    fn reference_ewma(closes: &[f64], lambda: f64) -> f64 {
+        let returns: Vec<f64> = closes.windows(2).map(|pair| (pair[1] / pair[0]).ln()).collect();
+        let n = returns.len();
+        let weights: Vec<f64> = (0..n).map(|i| lambda.powi((n - 1 - i) as i32)).collect();
+        let total: f64 = weights.iter().sum();
+        let mean = returns.iter().zip(&weights).map(|(r, w)| w * r).sum::<f64>() / total;
+        let second = returns.iter().zip(&weights).map(|(r, w)| w * r * r).sum::<f64>() / total;
+        ((second - mean * mean) * 252.0).sqrt()
+    }
+
+    #[test]
+    fn ewma_matches_the_host_reference() {
+        for lambda in [0.5, 0.9, 0.94, 0.99] {
+            let vol = ewma_vol(&series(&CLOSES), lambda).unwrap();
+            assert!((vol - reference_ewma(&CLOSES, lambda)).abs() < 1e-10, "lambda = {lambda}");
+        }
+    }
+
+    #[test]
+    fn ewma_with_unit_lambda_is_the_population_std() {
+        let returns: Vec<f64> = CLOSES.windows(2).map(|pair| (pair[1] / pair[0]).ln()).collect();
+        let n = returns.len() as f64;
+        let mean = returns.iter().sum::<f64>() / n;
+        let variance = returns.iter().map(|r| (r - mean) * (r - mean)).sum::<f64>() / n;
+        let vol = ewma_vol(&series(&CLOSES), 1.0).unwrap();
+        assert!((vol - (variance * 252.0).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn ewma_rejects_bad_parameters_and_series() {
+        for lambda in [0.0, 1.5, f64::NAN] {
+            assert_eq!(ewma_vol(&series(&CLOSES), lambda), Err(SeriesError::InvalidParameter));
+        }
+        let mut negative = CLOSES;
+        negative[3] = -1.0;
+        assert_eq!(ewma_vol(&series(&negative), 0.94), Err(SeriesError::NonPositivePrice));
+        let mut unannualized = series(&CLOSES);
+        unannualized.periods_per_year = 0.0;
+        assert_eq!(ewma_vol(&unannualized, 0.94), Err(SeriesError::InvalidPeriodsPerYear));
+        let constant = [100.0; 12];
+        assert_eq!(
+            price_with_ewma(&ewma_input(&constant, 0.94), &policy()).map(|estimate| estimate.vol),
+            Err(SeriesError::DegenerateVolatility)
+        );
+    }
+
+    #[test]
+    fn series_lengths_are_capped_at_both_ends() {
+        let vol = |len| ewma_vol(&series(&alternating(100.0, 101.0, len)), 0.94);
+        assert_eq!(vol(MIN_SERIES_LEN - 1), Err(SeriesError::TooShort));
+        assert!(vol(MIN_SERIES_LEN).is_ok());
+        assert!(vol(MAX_SERIES_LEN).is_ok());
+        assert_eq!(vol(MAX_SERIES_LEN + 1), Err(SeriesError::TooLong));
+    }
+
+    #[test]
+    fn ewma_estimates_outside_the_policy_are_rejected() {
+        // ln 2 per day annualizes to about 11, far above max_iv.
+        let wild = price_with_ewma(&ewma_input(&alternating(100.0, 200.0, 20), 0.94), &policy());
+        assert_eq!(wild.map(|estimate| estimate.vol), Err(SeriesError::OutOfPolicy(BoundViolation::IvAboveMax)));
+        // 1e-6 per day annualizes to about 1.6e-5, below min_iv.
+        let calm = price_with_ewma(&ewma_input(&alternating(100.0, 100.0001, 20), 0.94), &policy());
+        assert_eq!(calm.map(|estimate| estimate.vol), Err(SeriesError::OutOfPolicy(BoundViolation::IvBelowMin)));
+        assert!(price_with_ewma(&ewma_input(&CLOSES, 0.94), &policy()).is_ok());
+    }
+
+    fn garch_input(params: GarchParams, horizon: u32) -> GarchInput {
+        GarchInput {
+            price: 100.0,
+            strike: 105.0,
+            time: 1.0,
+            rate: 0.05,
+            series: series(&CLOSES),
+            params,
+            horizon,
+        }
+    }
+
+    /// Runs the recursion, then steps the expected variance forward one period at a time
+    /// instead of using the closed form.
+    fn reference_garch(closes: &[f64], params: &GarchParams, horizon: u32) -> f64 {
+        let returns: Vec<f64> = closes.windows(2).map(|pair| (pair[1] / pair[0]).ln()).collect();
+        let mut variance = returns.iter().map(|r| r * r).sum::<f64>() / returns.len() as f64;
+        for r in &returns {
+            variance = params.omega + params.alpha * r * r + params.beta * variance;
+        }
+        for _ in 1..horizon {
+            variance = params.omega + (params.alpha + params.beta) * variance;
+        }
+        (variance * 252.0).sqrt()
+    }
+
+    #[test]
+    fn garch_matches_the_host_reference() {
+        let params = GarchParams { omega: 0.000002, alpha: 0.08, beta: 0.9 };
+        for horizon in [1, 2, 5, 21, 63] {
+            let vol = garch_vol(&series(&CLOSES), &params, horizon).unwrap();
+            assert!((vol - reference_garch(&CLOSES, &params, horizon)).abs() < 1e-10, "horizon {horizon}");
+        }
+    }
+
+    #[test]
+    fn garch_near_the_unit_root_stays_finite() {
+        let params = GarchParams { omega: 0.0000001, alpha: 0.05, beta: 0.9499 };
+        for horizon in [1, 250, 5000] {
+            let vol = garch_vol(&series(&CLOSES), &params, horizon).unwrap();
+            assert!(vol.is_finite() && vol > 0.0);
+            assert!((vol - reference_garch(&CLOSES, &params, horizon)).abs() < 1e-10, "horizon {horizon}");
+        }
+        let unit_root = GarchParams { omega: 0.0000001, alpha: 0.05, beta: 0.95 };
+        assert_eq!(garch_vol(&series(&CLOSES), &unit_root, 1), Err(SeriesError::NonStationary));
+    }
+
+    #[test]
+    fn garch_forecast_converges_to_the_unconditional_vol() {
+        let params = GarchParams { omega: 0.000002, alpha: 0.08, beta: 0.9 };
+        let target = (params.omega / (1.0 - params.alpha - params.beta) * 252.0).sqrt();
+        let mut previous_gap = f64::INFINITY;
+        for horizon in [1, 10, 100, 1000, 2000] {
+            let gap = (garch_vol(&series(&CLOSES), &params, horizon).unwrap() - target).abs();
+            assert!(gap < previous_gap, "horizon {horizon} is no closer to the unconditional vol");
+            previous_gap = gap;
+        }
+        assert!(previous_gap < 1e-12);
+    }
+
+    #[test]
+    fn garch_rejects_bad_parameters() {
+        for params in [
+            GarchParams { omega: 0.0, alpha: 0.08, beta: 0.9 },
+            GarchParams { omega: 0.000002, alpha: -0.01, beta: 0.9 },
+            GarchParams { omega: 0.000002, alpha: f64::NAN, beta: 0.9 },
+        ] {
+            assert_eq!(garch_vol(&series(&CLOSES), &params, 1), Err(SeriesError::InvalidParameter));
+        }
+        let params = GarchParams { omega: 0.000002, alpha: 0.08, beta: 0.9 };
+        assert_eq!(garch_vol(&series(&CLOSES), &params, 0), Err(SeriesError::InvalidParameter));
+    }
+
+    #[test]
+    fn garch_forecasts_outside_the_policy_are_rejected() {
+        // An unconditional per-period variance of 1 annualizes to sqrt(252), far above max_iv.
+        let explosive = GarchParams { omega: 0.02, alpha: 0.08, beta: 0.9 };
+        let estimate = price_with_garch(&garch_input(explosive, 21), &policy());
+        assert_eq!(estimate.map(|estimate| estimate.vol), Err(SeriesError::OutOfPolicy(BoundViolation::IvAboveMax)));
+        let params = GarchParams { omega: 0.000002, alpha: 0.08, beta: 0.9 };
+        assert!(price_with_garch(&garch_input(params, 21), &policy()).is_ok());
+    }
+}
@@ -1,3 +1,5 @@
 fn main() {
+    // The program depends on the shared types, which build_program does not watch.
+    println!("cargo:rerun-if-changed=../common/src");
     sp1_helper::build_program(&format!("{}/../program", env!("CARGO_MANIFEST_DIR")));
 }
@@ -0,0 +1,186 @@
+//! Attesting a proof: the proof itself, and the two legs it cannot prove, checked off-circuit
+//! against the chain. The committed request hash must be the sha256 of a blob in the committed
+//! block, and that block must be finalized and canonical.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use common::{DaInclusion, Header, SCHEMA_VERSION};
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Proof, SP1VerifyingKey};
+
+/// The node queries attestation needs, behind a trait so that the checks can run against an
+/// in-memory chain in tests.
+#[async_trait]
+pub trait ChainReader: Send + Sync {
+    type Blob: AsRef<[u8]> + Send;
+
+    /// Number of the latest finalized block.
+    async fn finalized_number(&self) -> anyhow::Result<u32>;
+
+    /// Hash of the canonical block at `number`, if the chain has one.
+    async fn block_hash(&self, number: u32) -> anyhow::Result<Option<[u8; 32]>>;
+
+    /// The data submitted by every submit_data extrinsic in the block with `hash`.
+    async fn blobs(&self, hash: [u8; 32]) -> anyhow::Result<Vec<Self::Blob>>;
+}
+
+/// Checks that `inclusion` names a finalized block on the canonical chain, and that the block
+/// holds a data submission whose sha256 is `input_hash`.
+pub async fn check_inclusion<C: ChainReader>(
+    chain: &C,
+    inclusion: &DaInclusion,
+    input_hash: [u8; 32],
+) -> anyhow::Result<()> {
+    let finalized = chain.finalized_number().await?;
+    if finalized < inclusion.block_number {
+        return Err(anyhow!(
+            "Block #{} is not finalized yet; the finalized head is #{}.",
+            inclusion.block_number,
+            finalized
+        ));
+    }
+
+    if chain.block_hash(inclusion.block_number).await? != Some(inclusion.block_hash) {
+        return Err(anyhow!(
+            "Block #{} on the finalized chain is not 0x{}.",
+            inclusion.block_number,
+            hex::encode(inclusion.block_hash)
+        ));
+    }
+
+    let included = chain
+        .blobs(inclusion.block_hash)
+        .await?
+        .iter()
+        .any(|blob| <[u8; 32]>::from(Sha256::digest(blob.as_ref())) == input_hash);
+    if !included {
+        return Err(anyhow!(
+            "Block #{} holds no blob with sha256 0x{}.",
+            inclusion.block_number,
+            hex::encode(input_hash)
+        ));
+    }
+
+    Ok(())
+}
+
+/// Attests `proof` on all three legs: it verifies against `vk`, and the inclusion its header
+/// commits passes [`check_inclusion`]. Returns the committed header.
+pub async fn attest<C: ChainReader>(
+    client: &ProverClient,
+    vk: &SP1VerifyingKey,
+    proof: &SP1Proof,
+    chain: &C,
+) -> anyhow::Result<Header> {
+    client
+        .verify(proof, vk)
+        .map_err(|err| anyhow!("Proof verification failed: {:?}", err))?;
+
+    let header = proof.public_values.clone().read::<Header>();
+    if header.schema_version != SCHEMA_VERSION {
+        return Err(anyhow!(
+            "Public values use schema version {}, this script reads version {}.",
+            header.schema_version,
+            SCHEMA_VERSION
+        ));
+    }
+
+    check_inclusion(chain, &header.inclusion, header.input_hash).await?;
+
+    Ok(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// A chain held in memory: canonical blocks by number, each with its hash and blobs.
+    struct FakeChain {
+        finalized: u32,
+        blocks: HashMap<u32, ([u8; 32], Vec<Vec<u8>>)>,
+    }
+
+    #[async_trait]
+    impl ChainReader for FakeChain {
+        type Blob = Vec<u8>;
+
+        async fn finalized_number(&self) -> anyhow::Result<u32> {
+            Ok(self.finalized)
+        }
+
+        async fn block_hash(&self, number: u32) -> anyhow::Result<Option<[u8; 32]>> {
+            Ok(self.blocks.get(&number).map(|(hash, _)| *hash))
+        }
+
+        async fn blobs(&self, hash: [u8; 32]) -> anyhow::Result<Vec<Vec<u8>>> {
+            self.blocks
+                .values()
+                .find(|(block_hash, _)| *block_hash == hash)
+                .map(|(_, blobs)| blobs.clone())
+                .ok_or_else(|| anyhow!("no block 0x{}", hex::encode(hash)))
+        }
+    }
+
+    const BLOB: &[u8] = b"request blob";
+
+    /// Blocks 1 to 3 are finalized and block 4 is not; block 2 holds [`BLOB`] next to another
+    /// submission.
+    fn chain() -> FakeChain {
+        let blocks = (1..=4)
+            .map(|number| (number, ([number as u8; 32], vec![b"other blob".to_vec()])))
+            .collect::<HashMap<_, _>>();
+        let mut chain = FakeChain { finalized: 3, blocks };
+        chain.blocks.get_mut(&2).unwrap().1.push(BLOB.to_vec());
+        chain.blocks.get_mut(&4).unwrap().1.push(BLOB.to_vec());
+        chain
+    }
+
+    fn inclusion(block_number: u32) -> DaInclusion {
+        DaInclusion {
+            block_hash: [block_number as u8; 32],
+            block_number,
+        }
+    }
+
+    fn blob_hash() -> [u8; 32] {
+        Sha256::digest(BLOB).into()
+    }
+
+    /// Why [`check_inclusion`] rejects `inclusion` on [`chain`].
+    async fn rejection(inclusion: &DaInclusion, input_hash: [u8; 32]) -> String {
+        check_inclusion(&chain(), inclusion, input_hash)
+            .await
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn a_blob_in_a_finalized_canonical_block_is_attested() {
+        assert!(check_inclusion(&chain(), &inclusion(2), blob_hash()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn a_hash_no_blob_in_the_block_has_is_rejected() {
+        let mut other = blob_hash();
+        other[0] ^= 1;
+        assert!(rejection(&inclusion(2), other).await.contains("holds no blob"));
+        // The blob is in the chain, but not in the committed block.
+        assert!(rejection(&inclusion(3), blob_hash()).await.contains("holds no blob"));
+    }
+
+    #[tokio::test]
+    async fn a_block_hash_off_the_canonical_chain_is_rejected() {
+        let forked = DaInclusion {
+            block_hash: [0xff; 32],
+            block_number: 2,
+        };
+        assert!(rejection(&forked, blob_hash()).await.contains("on the finalized chain is not"));
+    }
+
+    #[tokio::test]
+    async fn a_block_above_the_finalized_head_is_rejected() {
+        // Block 4 is canonical and holds the blob, but is not finalized.
+        assert!(rejection(&inclusion(4), blob_hash()).await.contains("not finalized yet"));
+    }
+}
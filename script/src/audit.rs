@@ -0,0 +1,180 @@
+//! Verifying every proof artifact in a directory, for `verify-all`.
+
+use sp1_sdk::{ProverClient, SP1Proof, SP1VerifyingKey};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+pub enum Outcome {
+    Passed,
+    Failed(String),
+    /// The file is not a proof artifact at all.
+    Skipped(String),
+}
+
+/// Corrupt input may make loading or verification panic rather than error; either way the walk
+/// carries on with the other artifacts.
+fn verify_artifact(client: &ProverClient, vk: &SP1VerifyingKey, path: &Path) -> Outcome {
+    let proof = match panic::catch_unwind(|| SP1Proof::load(path)) {
+        Ok(Ok(proof)) => proof,
+        Ok(Err(err)) => return Outcome::Skipped(format!("not a proof artifact: {:?}", err)),
+        Err(_) => return Outcome::Skipped("not a proof artifact".to_string()),
+    };
+    match panic::catch_unwind(AssertUnwindSafe(|| client.verify(&proof, vk))) {
+        Ok(Ok(())) => Outcome::Passed,
+        Ok(Err(err)) => Outcome::Failed(format!("{:?}", err)),
+        Err(_) => Outcome::Failed("verifier panicked".to_string()),
+    }
+}
+
+/// The outcome of every artifact in a directory, in path order.
+pub struct Report {
+    pub outcomes: Vec<(PathBuf, Outcome)>,
+}
+
+impl Report {
+    fn count(&self, matches: impl Fn(&Outcome) -> bool) -> usize {
+        self.outcomes.iter().filter(|(_, outcome)| matches(outcome)).count()
+    }
+
+    pub fn passed(&self) -> usize {
+        self.count(|outcome| matches!(outcome, Outcome::Passed))
+    }
+
+    pub fn failed(&self) -> usize {
+        self.count(|outcome| matches!(outcome, Outcome::Failed(_)))
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.count(|outcome| matches!(outcome, Outcome::Skipped(_)))
+    }
+
+    /// 1 if any artifact failed to verify; skipped files alone do not fail the audit.
+    pub fn exit_code(&self) -> i32 {
+        if self.failed() > 0 {
+            1
+        } else {
+            0
+        }
+    }
+
+    pub fn print(&self) {
+        println!(
+            "total: {}, passed: {}, failed: {}, skipped: {}",
+            self.outcomes.len(),
+            self.passed(),
+            self.failed(),
+            self.skipped()
+        );
+        for (path, outcome) in &self.outcomes {
+            match outcome {
+                Outcome::Passed => {}
+                Outcome::Failed(reason) => println!("FAILED  {}: {}", path.display(), reason),
+                Outcome::Skipped(reason) => println!("SKIPPED {}: {}", path.display(), reason),
+            }
+        }
+    }
+}
+
+/// Verifies every file in `dir` against `vk` on `workers` threads.
+pub fn verify_dir(client: &ProverClient, vk: &SP1VerifyingKey, dir: &Path, workers: usize) -> Report {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .expect("reading the proof directory failed")
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+
+    // Workers pull the next unclaimed artifact until none are left.
+    let next = AtomicUsize::new(0);
+    let outcomes = Mutex::new(Vec::with_capacity(paths.len()));
+    std::thread::scope(|scope| {
+        for _ in 0..workers.max(1).min(paths.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = paths.get(index) else {
+                    break;
+                };
+                let outcome = verify_artifact(client, vk, path);
+                outcomes.lock().unwrap().push((path.clone(), outcome));
+            });
+        }
+    });
+
+    let mut outcomes = outcomes.into_inner().unwrap();
+    outcomes.sort_by(|a, b| a.0.cmp(&b.0));
+    Report { outcomes }
+}
+
+/// Loads a verifying key the script saved with bincode.
+pub fn load_vk(path: &Path) -> SP1VerifyingKey {
+    let bytes = std::fs::read(path).expect("reading the verifying key failed");
+    bincode::deserialize(&bytes).expect("parsing the verifying key failed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/verify-all");
+
+    /// `black-scholes.bin` is a proof of `program.elf`, both as committed with the original
+    /// script. The ELF is kept next to the proof because `program/elf` is rebuilt with every
+    /// program change, which changes the verifying key.
+    fn fixture(name: &str) -> Vec<u8> {
+        std::fs::read(Path::new(FIXTURES).join(name)).unwrap()
+    }
+
+    #[test]
+    fn counts_good_tampered_and_unrelated_artifacts() {
+        let dir = std::env::temp_dir().join(format!("verify-all-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let proof = fixture("black-scholes.bin");
+        std::fs::write(dir.join("a.bin"), &proof).unwrap();
+        std::fs::write(dir.join("b.bin"), &proof).unwrap();
+
+        // The proof with the first element of its first shard's main commitment changed by one.
+        // It still loads: bincode puts the shard count first, then the commitment's field
+        // elements as plain u32s, and field elements are not range-checked on load. The
+        // verifier derives its challenges from the commitment, so the openings no longer check
+        // out, whatever it does or does not check about the public values.
+        let mut tampered = proof.clone();
+        assert_eq!(tampered[..8], 1u64.to_le_bytes(), "the fixture is not a one-shard proof");
+        tampered[8] ^= 1;
+        std::fs::write(dir.join("c.bin"), &tampered).unwrap();
+
+        std::fs::write(dir.join("notes.txt"), "not a proof").unwrap();
+
+        // The key as `--vk` loads it, round-tripped through the file the script saves.
+        let client = ProverClient::new();
+        let vk_path = dir.join("vk.key");
+        let (_, vk) = client.setup(&fixture("program.elf"));
+        std::fs::write(&vk_path, bincode::serialize(&vk).unwrap()).unwrap();
+        let vk = load_vk(&vk_path);
+        std::fs::remove_file(&vk_path).unwrap();
+
+        let report = verify_dir(&client, &vk, &dir, 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let names: Vec<(&str, &str)> = report
+            .outcomes
+            .iter()
+            .map(|(path, outcome)| {
+                let name = path.file_name().unwrap().to_str().unwrap();
+                let outcome = match outcome {
+                    Outcome::Passed => "passed",
+                    Outcome::Failed(_) => "failed",
+                    Outcome::Skipped(_) => "skipped",
+                };
+                (name, outcome)
+            })
+            .collect();
+        assert_eq!(
+            names,
+            [("a.bin", "passed"), ("b.bin", "passed"), ("c.bin", "failed"), ("notes.txt", "skipped")]
+        );
+        assert_eq!((report.passed(), report.failed(), report.skipped()), (2, 1, 1));
+        assert_eq!(report.exit_code(), 1);
+    }
+}
@@ -0,0 +1,47 @@
+//! Verifies every proof artifact in a directory against the program's verifying key and
+//! prints a summary report. Exits non-zero if any proof fails to verify.
+//!
+//! Usage: `verify-all <dir> [--vk <vk.bin>] [--workers <n>]`
+//!
+//! `--vk` takes the key the script saves next to its proofs; without it the key is derived from
+//! the ELF this binary was built with, which only matches proofs made by the same build.
+
+use option_pricer_script::audit::{load_vk, verify_dir};
+use option_pricer_script::ELF;
+use sp1_sdk::ProverClient;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_WORKERS: usize = 4;
+
+fn main() {
+    const USAGE: &str = "usage: verify-all <dir> [--vk <vk.bin>] [--workers <n>]";
+    let mut dir = None;
+    let mut vk_path = None;
+    let mut workers = DEFAULT_WORKERS;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--vk" => vk_path = Some(args.next().expect(USAGE)),
+            "--workers" => {
+                workers = args
+                    .next()
+                    .expect(USAGE)
+                    .parse::<usize>()
+                    .expect("workers must be a number")
+            }
+            _ if dir.is_none() => dir = Some(PathBuf::from(arg)),
+            _ => panic!("{}", USAGE),
+        }
+    }
+    let dir = dir.expect(USAGE);
+
+    let client = ProverClient::new();
+    let vk = match vk_path {
+        Some(path) => load_vk(Path::new(&path)),
+        None => client.setup(ELF).1,
+    };
+
+    let report = verify_dir(&client, &vk, &dir, workers);
+    report.print();
+    std::process::exit(report.exit_code());
+}
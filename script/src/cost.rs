@@ -0,0 +1,149 @@
+use serde::{Serialize, Deserialize};
+use std::time::Duration;
+
+/// Plancks per AVAIL.
+const PLANCKS_PER_AVAIL: f64 = 1e18;
+
+/// Rates used to turn a run's resource usage into an estimated cost, loaded from `cost.toml`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CostRates {
+    pub usd_per_avail: f64,
+    /// Estimated cost of local proving per second of wall-clock time.
+    pub usd_per_proving_second: f64,
+}
+
+impl CostRates {
+    pub fn load(path: &str) -> Self {
+        let config = std::fs::read_to_string(path).expect("reading the cost rates failed");
+        toml::from_str(&config).expect("parsing the cost rates failed")
+    }
+}
+
+/// What one proof cost. Components the run did not produce are `None` and reported as absent
+/// rather than as zero.
+#[derive(Clone, Debug)]
+pub struct CostReport {
+    /// The Avail fee paid for the blob, in plancks.
+    pub da_fee: Option<u128>,
+    pub blob_len: usize,
+    pub proving_time: Duration,
+    /// The number of options the proof prices, to amortize the cost over.
+    pub option_count: usize,
+}
+
+impl CostReport {
+    pub fn da_cost_usd(&self, rates: &CostRates) -> Option<f64> {
+        self.da_fee
+            .map(|fee| fee as f64 / PLANCKS_PER_AVAIL * rates.usd_per_avail)
+    }
+
+    pub fn proving_cost_usd(&self, rates: &CostRates) -> f64 {
+        self.proving_time.as_secs_f64() * rates.usd_per_proving_second
+    }
+
+    /// The sum of the components that are present.
+    pub fn total_cost_usd(&self, rates: &CostRates) -> f64 {
+        self.da_cost_usd(rates).unwrap_or(0.0) + self.proving_cost_usd(rates)
+    }
+
+    pub fn per_option_cost_usd(&self, rates: &CostRates) -> f64 {
+        self.total_cost_usd(rates) / self.option_count.max(1) as f64
+    }
+
+    /// The report as printed, one component per line.
+    pub fn render(&self, rates: &CostRates) -> String {
+        let mut report = String::from("Cost report:\n");
+        match (self.da_fee, self.da_cost_usd(rates)) {
+            (Some(fee), Some(usd)) => report.push_str(&format!(
+                "  DA fee:        {:.6} AVAIL (${:.6})\n",
+                fee as f64 / PLANCKS_PER_AVAIL,
+                usd
+            )),
+            _ => report.push_str("  DA fee:        absent\n"),
+        }
+        report.push_str(&format!("  Blob size:     {} bytes\n", self.blob_len));
+        report.push_str(&format!(
+            "  Proving time:  {:.3}s (estimated ${:.6})\n",
+            self.proving_time.as_secs_f64(),
+            self.proving_cost_usd(rates)
+        ));
+        let absent = if self.da_fee.is_none() {
+            " (DA fee absent)"
+        } else {
+            ""
+        };
+        report.push_str(&format!(
+            "  Total:         ${:.6}{}\n",
+            self.total_cost_usd(rates),
+            absent
+        ));
+        if self.option_count > 1 {
+            report.push_str(&format!(
+                "  Per option:    ${:.6} over {} options\n",
+                self.per_option_cost_usd(rates),
+                self.option_count
+            ));
+        }
+        report
+    }
+
+    pub fn print(&self, rates: &CostRates) {
+        print!("{}", self.render(rates));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATES: CostRates = CostRates {
+        usd_per_avail: 0.1,
+        usd_per_proving_second: 0.0005,
+    };
+
+    fn report(da_fee: Option<u128>, option_count: usize) -> CostReport {
+        CostReport {
+            da_fee,
+            blob_len: 120,
+            proving_time: Duration::from_secs(100),
+            option_count,
+        }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    #[test]
+    fn components_add_up() {
+        // 2 AVAIL at $0.1 plus 100 s at $0.0005, over four options.
+        let report = report(Some(2_000_000_000_000_000_000), 4);
+        assert!(close(report.da_cost_usd(&RATES).unwrap(), 0.2));
+        assert!(close(report.proving_cost_usd(&RATES), 0.05));
+        assert!(close(report.total_cost_usd(&RATES), 0.25));
+        assert!(close(report.per_option_cost_usd(&RATES), 0.0625));
+
+        let rendered = report.render(&RATES);
+        assert!(rendered.contains("DA fee:        2.000000 AVAIL ($0.200000)"));
+        assert!(rendered.contains("Total:         $0.250000\n"));
+        assert!(rendered.contains("Per option:    $0.062500 over 4 options"));
+    }
+
+    #[test]
+    fn missing_da_fee_is_absent_not_zero() {
+        let report = report(None, 1);
+        assert_eq!(report.da_cost_usd(&RATES), None);
+        assert!(close(report.total_cost_usd(&RATES), 0.05));
+
+        let rendered = report.render(&RATES);
+        assert!(rendered.contains("DA fee:        absent\n"));
+        assert!(rendered.contains("Total:         $0.050000 (DA fee absent)"));
+        assert!(!rendered.contains("AVAIL"));
+        assert!(!rendered.contains("Per option"));
+    }
+
+    #[test]
+    fn zero_options_do_not_divide_by_zero() {
+        assert!(close(report(None, 0).per_option_cost_usd(&RATES), 0.05));
+    }
+}
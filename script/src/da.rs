@@ -1,4 +1,3 @@
-use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use bytes::Bytes;
 use sp_core::H256;
@@ -10,8 +9,10 @@ use avail_subxt::{
     api::runtime_types::{da_control::pallet::Call, da_runtime::RuntimeCall::DataAvailability},
     primitives::AppUncheckedExtrinsic,
 };
-use codec::Encode;
+use codec::{Compact, Encode, Output};
 use anyhow::anyhow;
+use async_trait::async_trait;
+use common::DaInclusion;
 use avail_subxt::api::runtime_types::sp_core::bounded::bounded_vec::BoundedVec;
 use avail_subxt::primitives::AvailExtrinsicParams;
 use avail_subxt::{api, AvailConfig};
@@ -20,60 +21,87 @@ use sp_keyring::sr25519::sr25519::Pair;
 use subxt::tx::PairSigner;
 use subxt::OnlineClient;
 
-#[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize, PartialEq)]
-/// Simple structure that implements the Read trait for a buffer and  counts the number of bytes read from the beginning.
+use crate::attest::ChainReader;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "CountedBufReaderParts")]
+/// Simple structure that reads a blob buffer and  counts the number of bytes read from the beginning.
 /// Useful for the partial blob reading optimization: we know for each blob how many bytes have been read from the beginning.
 ///
 /// Because of soundness issues we cannot implement the Buf trait because the prover could get unproved blob data using the chunk method.
-pub struct CountedBufReader<B: bytes::Buf> {
+///
+/// The accumulator is the already-read prefix of the original blob, so advancing never copies:
+/// the read bytes are borrowed, or shared through [`Bytes::slice`], from the same buffer.
+pub struct CountedBufReader {
     /// The original blob data.
-    inner: B,
+    inner: Bytes,
 
-    /// An accumulator that stores the data read from the blob buffer into a vector.
-    /// Allows easy access to the data that has already been read
-    accumulator: Vec<u8>,
+    /// The number of bytes read from the beginning of the blob. Only `inner[..verified_len]`
+    /// is ever exposed. Never exceeds `inner.len()`: `new` starts at zero, `advance` clamps,
+    /// and deserialization rejects anything larger.
+    verified_len: usize,
+}
+
+/// The serialized fields of a [`CountedBufReader`], checked before one is built from them.
+#[derive(Deserialize)]
+struct CountedBufReaderParts {
+    inner: Bytes,
+    verified_len: usize,
+}
+
+impl TryFrom<CountedBufReaderParts> for CountedBufReader {
+    type Error = anyhow::Error;
+
+    fn try_from(parts: CountedBufReaderParts) -> Result<Self, Self::Error> {
+        if parts.verified_len > parts.inner.len() {
+            return Err(anyhow!(
+                "verified length {} exceeds the blob length {}",
+                parts.verified_len,
+                parts.inner.len()
+            ));
+        }
+        Ok(CountedBufReader {
+            inner: parts.inner,
+            verified_len: parts.verified_len,
+        })
+    }
 }
 
-impl<B: bytes::Buf> CountedBufReader<B> {
-    /// Creates a new buffer reader with counter from an objet that implements the buffer trait
-    pub fn new(inner: B) -> Self {
-        let buf_size = inner.remaining();
+impl CountedBufReader {
+    /// Creates a new buffer reader with counter from the blob bytes
+    pub fn new(inner: Bytes) -> Self {
         CountedBufReader {
             inner,
-            accumulator: Vec::with_capacity(buf_size),
+            verified_len: 0,
         }
     }
 
     /// Advance the accumulator by `num_bytes` bytes. If `num_bytes` is greater than the length
     /// of remaining unverified data, then all remaining unverified data is added to the accumulator.
     pub fn advance(&mut self, num_bytes: usize) {
-        let requested = num_bytes;
-        let remaining = self.inner.remaining();
-        if remaining == 0 {
-            return;
-        }
-        // `Buf::advance` would panic if `num_bytes` was greater than the length of the remaining unverified data,
-        // but we just advance to the end of the buffer.
-        let num_to_read = core::cmp::min(remaining, requested);
-        // Extend the inner vector with zeros (copy_to_slice requires the vector to have
-        // the correct *length* not just capacity)
-        self.accumulator
-            .resize(self.accumulator.len() + num_to_read, 0);
-
-        // Use copy_to_slice to overwrite the zeros we just added
-        let accumulator_len = self.accumulator.len();
-        self.inner
-            .copy_to_slice(self.accumulator[accumulator_len - num_to_read..].as_mut());
+        let total_len = self.total_len();
+        // We never read past the end of the buffer, we just advance to the end of it.
+        self.verified_len = core::cmp::min(total_len, self.verified_len.saturating_add(num_bytes));
     }
 
     /// Getter: returns a reference to an accumulator of the blob data read by the rollup
     pub fn accumulator(&self) -> &[u8] {
-        &self.accumulator
+        &self.inner[..self.verified_len]
+    }
+
+    /// Returns a shared handle on the accumulator, without copying the blob data
+    pub fn accumulator_bytes(&self) -> Bytes {
+        self.inner.slice(..self.verified_len)
+    }
+
+    /// Contains the length of the data read from the beginning of the blob
+    pub fn verified_len(&self) -> usize {
+        self.verified_len
     }
 
     /// Contains the total length of the data (length already read + length remaining)
     pub fn total_len(&self) -> usize {
-        self.inner.remaining() + self.accumulator.len()
+        self.inner.len()
     }
 }
 
@@ -119,25 +147,64 @@ impl<'a> TryFrom<&'a [u8]> for AvailAddress {
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct AvailBlobTransaction {
-    blob: CountedBufReader<Bytes>,
+    blob: CountedBufReader,
     hash: [u8; 32],
     address: AvailAddress,
 }
 
+/// The extrinsic format version, which the first encoded byte carries after the length.
+const EXTRINSIC_FORMAT_VERSION: u8 = 4;
+
+/// A SCALE output that feeds everything written to it into a blake2b-256 state.
+struct Blake2Output(blake2b_simd::State);
+
+impl Output for Blake2Output {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+}
+
+/// `blake2_256(&unchecked_extrinsic.encode())`, without materializing the encoding. It is
+/// streamed in the order `AppUncheckedExtrinsic::encode` writes it: the compact length of the
+/// rest, the version byte, the signature if any, then the call.
+fn extrinsic_hash(unchecked_extrinsic: &AppUncheckedExtrinsic) -> [u8; 32] {
+    let (version, signature_len) = match &unchecked_extrinsic.signature {
+        Some(signature) => (EXTRINSIC_FORMAT_VERSION | 0b1000_0000, signature.encoded_size()),
+        None => (EXTRINSIC_FORMAT_VERSION, 0),
+    };
+    let len = 1 + signature_len + unchecked_extrinsic.function.encoded_size();
+
+    let mut output = Blake2Output(blake2b_simd::Params::new().hash_length(32).to_state());
+    Compact(len as u32).encode_to(&mut output);
+    output.push_byte(version);
+    if let Some(signature) = &unchecked_extrinsic.signature {
+        signature.encode_to(&mut output);
+    }
+    unchecked_extrinsic.function.encode_to(&mut output);
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(output.0.finalize().as_bytes());
+    hash
+}
+
 impl AvailBlobTransaction {
-    pub fn new(unchecked_extrinsic: &AppUncheckedExtrinsic) -> anyhow::Result<Self> {
-        let address = match &unchecked_extrinsic.signature {
+    /// Takes ownership of the extrinsic so that the submitted data is moved into the blob
+    /// rather than copied.
+    pub fn new(unchecked_extrinsic: AppUncheckedExtrinsic) -> anyhow::Result<Self> {
+        let hash = extrinsic_hash(&unchecked_extrinsic);
+
+        let address = match unchecked_extrinsic.signature {
             //TODO: Handle other types of MultiAddress.
-            Some((subxt::utils::MultiAddress::Id(id), _, _)) => AvailAddress::from(id.clone().0),
+            Some((subxt::utils::MultiAddress::Id(id), _, _)) => AvailAddress::from(id.0),
             _ => {
                 return Err(anyhow!(
                     "Unsigned extrinsic being used to create AvailBlobTransaction."
                 ))
             }
         };
-        let blob = match &unchecked_extrinsic.function {
+        let blob = match unchecked_extrinsic.function {
             DataAvailability(Call::submit_data { data }) => {
-                CountedBufReader::<Bytes>::new(Bytes::copy_from_slice(&data.0))
+                CountedBufReader::new(Bytes::from(data.0))
             }
             _ => {
                 return Err(anyhow!(
@@ -147,7 +214,7 @@ impl AvailBlobTransaction {
         };
 
         Ok(AvailBlobTransaction {
-            hash: sp_core_hashing::blake2_256(&unchecked_extrinsic.encode()),
+            hash,
             address,
             blob,
         })
@@ -157,6 +224,15 @@ impl AvailBlobTransaction {
         self.hash
     }
 
+    /// The whole submitted blob, shared with the transaction rather than copied. This is for
+    /// host-side attestation only: it bypasses the reader's verified-length accounting, which
+    /// anything feeding the blob to a prover must go through.
+    pub(crate) fn full_data(&self) -> Bytes {
+        let mut blob = self.blob.clone();
+        blob.advance(blob.total_len());
+        blob.accumulator_bytes()
+    }
+
     pub fn combine_hash(&self, hash: [u8; 32]) -> [u8; 32] {
         let mut combined_hashes: Vec<u8> = Vec::with_capacity(64);
         combined_hashes.extend_from_slice(hash.as_ref());
@@ -200,7 +276,53 @@ impl DaProvider {
     }
 }
 
-pub async fn send_transaction(da_provider: &DaProvider, blob: &[u8]) -> Result<(), anyhow::Error> {
+#[async_trait]
+impl ChainReader for DaProvider {
+    type Blob = Bytes;
+
+    async fn finalized_number(&self) -> anyhow::Result<u32> {
+        let rpc = self.node_client.rpc();
+        let finalized_hash = rpc.finalized_head().await?;
+        let finalized = rpc
+            .header(Some(finalized_hash))
+            .await?
+            .ok_or_else(|| anyhow!("Finalized block {:?} has no header.", finalized_hash))?;
+        Ok(finalized.number)
+    }
+
+    async fn block_hash(&self, number: u32) -> anyhow::Result<Option<[u8; 32]>> {
+        let hash = self.node_client.rpc().block_hash(Some(number.into())).await?;
+        Ok(hash.map(|hash| hash.0))
+    }
+
+    async fn blobs(&self, hash: [u8; 32]) -> anyhow::Result<Vec<Bytes>> {
+        let block = self
+            .node_client
+            .rpc()
+            .block(Some(hash.into()))
+            .await?
+            .ok_or_else(|| anyhow!("Block 0x{} has no body.", hex::encode(hash)))?;
+        Ok(block
+            .block
+            .extrinsics
+            .iter()
+            .filter_map(|extrinsic| AppUncheckedExtrinsic::try_from(extrinsic).ok())
+            .filter_map(|extrinsic| AvailBlobTransaction::new(extrinsic).ok())
+            .map(|transaction| transaction.full_data())
+            .collect())
+    }
+}
+
+/// The outcome of a finalized submission.
+#[derive(Clone, Debug)]
+pub struct DaReceipt {
+    pub inclusion: DaInclusion,
+    /// The fee actually paid, in plancks, when the block reports one for the extrinsic.
+    pub fee: Option<u128>,
+}
+
+/// Submits the blob and waits until the extrinsic is finalized.
+pub async fn send_transaction(da_provider: &DaProvider, blob: &[u8]) -> Result<DaReceipt, anyhow::Error> {
     let data_transfer = api::tx()
         .data_availability()
         .submit_data(BoundedVec(blob.to_vec()));
@@ -217,5 +339,163 @@ pub async fn send_transaction(da_provider: &DaProvider, blob: &[u8]) -> Result<(
 
     info!("Transaction submitted: {:#?}", h.extrinsic_hash());
 
-    Ok(())
-}
\ No newline at end of file
+    let in_block = h.wait_for_finalized().await?;
+    let events = in_block.wait_for_success().await?;
+    let fee = events
+        .find_first::<api::transaction_payment::events::TransactionFeePaid>()?
+        .map(|fee_paid| fee_paid.actual_fee);
+
+    let block_hash = in_block.block_hash();
+    let header = da_provider
+        .node_client
+        .rpc()
+        .header(Some(block_hash))
+        .await?
+        .ok_or_else(|| anyhow!("Finalized block {:?} has no header.", block_hash))?;
+
+    println!("Transaction finalized");
+
+    info!("Transaction finalized in block {:#?}", block_hash);
+
+    Ok(DaReceipt {
+        inclusion: DaInclusion {
+            block_hash: block_hash.0,
+            block_number: header.number,
+        },
+        fee,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    /// Counts the bytes allocated on the current thread, so that tests running in parallel do
+    /// not see each other's allocations.
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + layout.size()));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    fn allocated_during(f: impl FnOnce()) -> usize {
+        let before = ALLOCATED.with(Cell::get);
+        f();
+        ALLOCATED.with(Cell::get) - before
+    }
+
+    const MIB: usize = 1 << 20;
+
+    /// A signed `submit_data` extrinsic carrying `data`, as a block body would hold it.
+    fn submit_data_extrinsic(data: Vec<u8>) -> AppUncheckedExtrinsic {
+        AppUncheckedExtrinsic {
+            signature: Some((
+                subxt::utils::MultiAddress::Id(subxt::utils::AccountId32([7; 32])),
+                subxt::utils::MultiSignature::Sr25519([9; 64]),
+                AvailExtrinsicParams::new_with_app_id(0.into()),
+            )),
+            function: DataAvailability(Call::submit_data {
+                data: BoundedVec(data),
+            }),
+        }
+    }
+
+    #[test]
+    fn extrinsic_hash_matches_hashing_the_encoding() {
+        for len in [0, 1, 63, 64, 16_383, 16_384, MIB] {
+            let extrinsic = submit_data_extrinsic(vec![0x5a; len]);
+            assert_eq!(extrinsic_hash(&extrinsic), sp_core_hashing::blake2_256(&extrinsic.encode()));
+        }
+    }
+
+    #[test]
+    fn building_and_reading_a_mebibyte_blob_does_not_copy_it() {
+        let payload: Vec<u8> = (0..MIB).map(|i| i as u8).collect();
+        let extrinsic = submit_data_extrinsic(payload.clone());
+        let expected_hash = sp_core_hashing::blake2_256(&extrinsic.encode());
+
+        let mut transaction = None;
+        let mut read = 0;
+        let allocated = allocated_during(|| {
+            let built = AvailBlobTransaction::new(extrinsic).unwrap();
+            let mut blob = built.blob.clone();
+            while blob.verified_len() < blob.total_len() {
+                blob.advance(64 * 1024);
+                read += blob.accumulator().len();
+                assert_eq!(blob.accumulator_bytes().len(), blob.verified_len());
+            }
+            transaction = Some(built);
+        });
+        // Moving the payload in and sharing it out costs a few small control blocks; one copy
+        // of the blob would cost a whole mebibyte.
+        assert!(allocated < 4096, "allocated {allocated} bytes");
+
+        let transaction = transaction.unwrap();
+        assert_eq!(transaction.hash(), expected_hash);
+        assert_eq!(transaction.blob.accumulator(), b"");
+        assert_eq!(transaction.blob.total_len(), MIB);
+        assert_eq!(read, (1..=16).map(|chunk| chunk * 64 * 1024).sum::<usize>());
+        let mut blob = transaction.blob;
+        blob.advance(MIB);
+        assert_eq!(blob.accumulator(), payload.as_slice());
+    }
+
+    #[test]
+    fn full_data_shares_the_whole_blob_without_verifying_it() {
+        let payload: Vec<u8> = (0..MIB).map(|i| i as u8).collect();
+        let transaction = AvailBlobTransaction::new(submit_data_extrinsic(payload.clone())).unwrap();
+
+        let mut full_data = None;
+        let allocated = allocated_during(|| full_data = Some(transaction.full_data()));
+        assert!(allocated < 4096, "allocated {allocated} bytes");
+        assert_eq!(&full_data.unwrap()[..], payload.as_slice());
+        // Reading it for attestation does not count as verifying any of it.
+        assert_eq!(transaction.blob.verified_len(), 0);
+    }
+
+    #[test]
+    fn advance_clamps_to_the_blob_length() {
+        let mut blob = CountedBufReader::new(Bytes::from_static(b"blob"));
+        blob.advance(3);
+        assert_eq!(blob.accumulator(), b"blo");
+        blob.advance(usize::MAX);
+        assert_eq!(blob.accumulator(), b"blob");
+        assert_eq!(blob.verified_len(), blob.total_len());
+    }
+
+    #[test]
+    fn deserialization_rejects_a_verified_len_past_the_end() {
+        #[derive(Serialize)]
+        struct Parts {
+            inner: Bytes,
+            verified_len: usize,
+        }
+        let encode = |verified_len| {
+            bincode::serialize(&Parts {
+                inner: Bytes::from_static(b"blob"),
+                verified_len,
+            })
+            .unwrap()
+        };
+
+        let blob: CountedBufReader = bincode::deserialize(&encode(4)).unwrap();
+        assert_eq!(blob.accumulator(), b"blob");
+        assert!(bincode::deserialize::<CountedBufReader>(&encode(5)).is_err());
+    }
+}
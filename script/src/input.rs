@@ -0,0 +1,132 @@
+//! Host-side handling of requests. The request types themselves live in
+//! `option-pricer-common`, shared with the program.
+
+use anyhow::anyhow;
+use common::{PricingRequest, MAX_SERIES_LEN, MAX_TERM_POINTS, MAX_THETA_OFFSETS};
+
+/// Loads a request from a `.toml` file, or from JSON for any other extension. Both use the
+/// externally tagged layout, e.g. `{ "BlackScholes": { "price": 100.0, ... } }`.
+pub fn load(path: &str) -> PricingRequest {
+    let request = std::fs::read_to_string(path).expect("reading the request failed");
+    if path.ends_with(".toml") {
+        toml::from_str(&request).expect("parsing the request failed")
+    } else {
+        serde_json::from_str(&request).expect("parsing the request failed")
+    }
+}
+
+/// The request exactly as the program reads it.
+pub fn to_bytes(request: &PricingRequest) -> Vec<u8> {
+    bincode::serialize(request).unwrap()
+}
+
+/// Fails if the request exceeds one of the program's size limits, which the program would
+/// otherwise prove as a rejection.
+pub fn check_limits(request: &PricingRequest) -> anyhow::Result<()> {
+    match request {
+        PricingRequest::TermStructure(options) if options.len() > MAX_TERM_POINTS => Err(anyhow!(
+            "{} expiries exceed the limit of {}",
+            options.len(),
+            MAX_TERM_POINTS
+        )),
+        PricingRequest::Ewma(input) if input.series.closes.len() > MAX_SERIES_LEN => Err(anyhow!(
+            "{} closes exceed the limit of {}",
+            input.series.closes.len(),
+            MAX_SERIES_LEN
+        )),
+        PricingRequest::Garch(input) if input.series.closes.len() > MAX_SERIES_LEN => Err(anyhow!(
+            "{} closes exceed the limit of {}",
+            input.series.closes.len(),
+            MAX_SERIES_LEN
+        )),
+        PricingRequest::ThetaSchedule(input) if input.offsets_days.len() > MAX_THETA_OFFSETS => {
+            Err(anyhow!(
+                "{} offsets exceed the limit of {}",
+                input.offsets_days.len(),
+                MAX_THETA_OFFSETS
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The number of options one proof of this request prices.
+pub fn option_count(request: &PricingRequest) -> usize {
+    match request {
+        PricingRequest::TermStructure(options) => options.len(),
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{
+        BlackScholesInput, EwmaInput, GarchInput, GarchParams, PriceSeries, ThetaScheduleInput,
+    };
+
+    #[test]
+    fn example_requests_load() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/requests");
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            load(path.to_str().unwrap());
+        }
+    }
+
+    #[test]
+    fn toml_and_json_requests_agree() {
+        let json = r#"{ "BlackScholes": { "price": 100.0, "strike": 105.0, "iv": 0.2, "time": 1.0, "rate": 0.05 } }"#;
+        let toml = "[BlackScholes]\nprice = 100.0\nstrike = 105.0\niv = 0.2\ntime = 1.0\nrate = 0.05\n";
+        let from_json: PricingRequest = serde_json::from_str(json).unwrap();
+        let from_toml: PricingRequest = toml::from_str(toml).unwrap();
+        assert_eq!(to_bytes(&from_json), to_bytes(&from_toml));
+        assert_eq!(to_bytes(&from_json), to_bytes(&PricingRequest::BlackScholes(BlackScholesInput::default())));
+    }
+
+    #[test]
+    fn oversized_requests_fail_the_limit_check() {
+        let term = |count| PricingRequest::TermStructure(vec![BlackScholesInput::default(); count]);
+        assert!(check_limits(&term(MAX_TERM_POINTS)).is_ok());
+        assert!(check_limits(&term(MAX_TERM_POINTS + 1)).is_err());
+
+        let series = |count| PriceSeries {
+            closes: vec![100.0; count],
+            periods_per_year: 252.0,
+        };
+        let ewma = |count| {
+            PricingRequest::Ewma(EwmaInput {
+                price: 100.0,
+                strike: 105.0,
+                time: 1.0,
+                rate: 0.05,
+                series: series(count),
+                lambda: 0.94,
+            })
+        };
+        let garch = |count| {
+            PricingRequest::Garch(GarchInput {
+                price: 100.0,
+                strike: 105.0,
+                time: 1.0,
+                rate: 0.05,
+                series: series(count),
+                params: GarchParams { omega: 0.000002, alpha: 0.08, beta: 0.9 },
+                horizon: 21,
+            })
+        };
+        assert!(check_limits(&ewma(MAX_SERIES_LEN)).is_ok());
+        assert!(check_limits(&ewma(MAX_SERIES_LEN + 1)).is_err());
+        assert!(check_limits(&garch(MAX_SERIES_LEN)).is_ok());
+        assert!(check_limits(&garch(MAX_SERIES_LEN + 1)).is_err());
+
+        let theta = |count| {
+            PricingRequest::ThetaSchedule(ThetaScheduleInput {
+                option: BlackScholesInput::default(),
+                offsets_days: vec![30; count],
+            })
+        };
+        assert!(check_limits(&theta(MAX_THETA_OFFSETS)).is_ok());
+        assert!(check_limits(&theta(MAX_THETA_OFFSETS + 1)).is_err());
+    }
+}
@@ -0,0 +1,12 @@
+//! The host side of the option pricer, shared by the proving script and `verify-all`.
+
+pub mod attest;
+pub mod audit;
+pub mod cost;
+pub mod da;
+pub mod input;
+pub mod policy;
+pub mod summary;
+
+/// The ELF we want to execute inside the zkVM.
+pub const ELF: &[u8] = include_bytes!("../../program/elf/riscv32im-succinct-zkvm-elf");
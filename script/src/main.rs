@@ -1,43 +1,31 @@
-mod da;
-
-use da::{send_transaction, DaProvider, DaServiceConfig};
+use option_pricer_script::attest::attest;
+use option_pricer_script::cost::{CostRates, CostReport};
+use option_pricer_script::da::{send_transaction, DaProvider, DaServiceConfig};
+use option_pricer_script::summary::{print_summary, PublicValues};
+use option_pricer_script::{input, policy, ELF};
+use common::{BlackScholesInput, PricingRequest};
+use sha2::{Digest, Sha256};
+use std::time::Instant;
 use subxt::tx::PairSigner;
 use avail_subxt::{api, AvailConfig};
 use sp1_sdk::{utils, ProverClient, SP1Proof, SP1Stdin};
-use serde::{Serialize, Deserialize};
 use subxt::{
     ext::sp_core::sr25519::Pair,
     ext::sp_core::Pair as PairT,
     ext::sp_core::H256 as AvailH256,
 };
 
-/// The ELF we want to execute inside the zkVM.
-const ELF: &[u8] = include_bytes!("../../program/elf/riscv32im-succinct-zkvm-elf");
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct BlackScholesInput {
-    pub price: f64,
-    pub strike: f64,
-    pub iv: f64,
-    pub time: f64,
-    pub rate: f64,
-}
-
-impl BlackScholesInput {
-    pub fn to_bytes(&self) -> Vec<u8> {
-        bincode::serialize(self).unwrap()
-    }
+/// The value following `flag` on the command line, if the flag is present.
+fn arg_value(flag: &str) -> Option<String> {
+    std::env::args().skip_while(|arg| arg != flag).nth(1)
 }
 
-impl Default for BlackScholesInput {
-    fn default() -> Self {
-        BlackScholesInput {
-            price: 100.0,
-            strike: 105.0,
-            iv: 0.2,
-            time: 1.0,
-            rate: 0.05,
-        }
+fn da_config() -> DaServiceConfig {
+    DaServiceConfig {
+        node_client_url: "wss://turing-rpc.avail.so:443/ws".to_string(),
+        //TODO: Safer strategy to load seed so it is not accidentally revealed.
+        seed: "bulk impact process private orange motion roof force clean recall filter secret".to_string(),
+        app_id: 0,
     }
 }
 
@@ -47,32 +35,92 @@ async fn main() {
     // utils::setup_tracer();
     utils::setup_logger();
 
-    let input = BlackScholesInput::default();
+    // `attest [--proof <path>]` attests a saved proof instead of producing one.
+    if std::env::args().nth(1).as_deref() == Some("attest") {
+        let path = arg_value("--proof").unwrap_or_else(|| "proof-with-pis.bin".to_string());
+        let proof = SP1Proof::load(&path).expect("loading proof failed");
+        let client = ProverClient::new();
+        let (_, vk) = client.setup(ELF);
+        let da_provider = DaProvider::new(da_config()).await;
+        let header = attest(&client, &vk, &proof, &da_provider)
+            .await
+            .expect("attestation failed");
+        println!(
+            "Attested: request 0x{} was available in finalized Avail block #{} (0x{})",
+            hex::encode(header.input_hash),
+            header.inclusion.block_number,
+            hex::encode(header.inclusion.block_hash)
+        );
+        return;
+    }
 
-    let da_config = DaServiceConfig {
-        node_client_url: "wss://turing-rpc.avail.so:443/ws".to_string(),
-        //TODO: Safer strategy to load seed so it is not accidentally revealed.
-        seed: "bulk impact process private orange motion roof force clean recall filter secret".to_string(),
-        app_id: 0,
+    // `--request <path>` selects the mode; see `requests/` for one example per mode. Without it
+    // the default Black-Scholes option is priced.
+    let request = match arg_value("--request") {
+        Some(path) => input::load(&path),
+        None => PricingRequest::BlackScholes(BlackScholesInput::default()),
     };
 
-    let da_provder = DaProvider::new(da_config).await;
+    // `--outputs <path>` writes the canonical encoding of a batch's outputs, whose digest the
+    // proof commits, for the off-chain data pipeline. Only term-structure requests, e.g.
+    // `--request requests/term-structure.json`, are batches.
+    let outputs_path = arg_value("--outputs");
+
+    // The program rejects oversized requests too, but refusing here is cheaper than proving
+    // the rejection.
+    input::check_limits(&request).expect("request exceeds a program limit");
 
-    let blob = input.to_bytes();
+    // The program rejects out-of-policy requests too, and its check is the authoritative one,
+    // but refusing here is cheaper than proving the rejection.
+    let policy = policy::load("policy.toml");
+    policy
+        .validate(&request)
+        .expect("request violates the bounds policy");
+    let cost_rates = CostRates::load("cost.toml");
 
-    send_transaction(&da_provder, &blob).await.unwrap();
+    let da_provder = DaProvider::new(da_config()).await;
+
+    let blob = input::to_bytes(&request);
+
+    // The proof commits the finalized inclusion block, so submission has to finish first.
+    let receipt = send_transaction(&da_provder, &blob).await.unwrap();
 
     let mut stdin = SP1Stdin::new();
 
-    stdin.write(&input);
+    // The program hashes exactly these bytes, so the committed request hash is the blob's.
+    stdin.write_slice(&blob);
+    stdin.write(&policy);
+    stdin.write(&receipt.inclusion);
 
     let client = ProverClient::new();
     let (pk, vk) = client.setup(ELF);
+    // `verify-all --vk vk.bin` checks proofs against this key rather than its own build's.
+    std::fs::write("vk.bin", bincode::serialize(&vk).unwrap()).expect("saving the verifying key failed");
 
+    let proving_started = Instant::now();
     let proof = client.prove(&pk, stdin).expect("proving failed");
-
-    // Verify proof.
-    client.verify(&proof, &vk).expect("verification failed");
+    let proving_time = proving_started.elapsed();
+
+    // Verify the proof and attest the inclusion it commits, which must be the blob just
+    // submitted.
+    let header = attest(&client, &vk, &proof, &da_provder)
+        .await
+        .expect("attestation failed");
+    let blob_hash: [u8; 32] = Sha256::digest(&blob).into();
+    assert_eq!(header.input_hash, blob_hash, "committed request hash is not the submitted blob's");
+
+    print_summary(
+        &PublicValues::decode(&request, &mut proof.public_values.clone()),
+        outputs_path.as_deref(),
+    );
+
+    let cost_report = CostReport {
+        da_fee: receipt.fee,
+        blob_len: blob.len(),
+        proving_time,
+        option_count: input::option_count(&request),
+    };
+    cost_report.print(&cost_rates);
 
     // Test a round trip of proof serialization and deserialization.
     proof
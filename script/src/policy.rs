@@ -0,0 +1,25 @@
+//! Loading the bounds policy. The script checks a request against it before submitting or
+//! proving; the program's check is authoritative.
+
+use common::BoundsPolicy;
+
+pub fn load(path: &str) -> BoundsPolicy {
+    let config = std::fs::read_to_string(path).expect("reading the bounds policy failed");
+    toml::from_str(&config).expect("parsing the bounds policy failed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_policy_accepts_the_example_requests() {
+        let policy = load(concat!(env!("CARGO_MANIFEST_DIR"), "/policy.toml"));
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/requests");
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let request = crate::input::load(path.to_str().unwrap());
+            assert_eq!(policy.validate(&request), Ok(()), "{}", path.display());
+        }
+    }
+}
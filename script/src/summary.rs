@@ -0,0 +1,507 @@
+//! Decodes the committed public values of a proof and prints them.
+
+use serde::Serialize;
+use sp1_sdk::SP1PublicValues;
+
+use common::{
+    compute_outputs_digest, Greeks, GreeksInput, Header, ImpliedDividend, ImpliedDividendError,
+    ImpliedDividendInput, PricingRequest, QuantoError, QuantoInput, QuantoPrices, SeriesError,
+    TermStructure, TermStructureError, ThetaError, ThetaSchedule, VolEstimate, SCHEMA_VERSION,
+};
+
+/// What each mode commits after the header, in commit order.
+#[derive(Clone, Debug)]
+pub enum Outputs {
+    BlackScholes {
+        call: f64,
+        put: f64,
+    },
+    ImpliedDividend {
+        input: ImpliedDividendInput,
+        implied: Result<ImpliedDividend, ImpliedDividendError>,
+    },
+    TermStructure {
+        structure: Result<TermStructure, TermStructureError>,
+        /// Committed after an accepted structure only.
+        outputs_digest: Option<[u8; 32]>,
+    },
+    Quanto {
+        input: QuantoInput,
+        prices: Result<QuantoPrices, QuantoError>,
+    },
+    Greeks {
+        input: GreeksInput,
+        greeks: Greeks,
+    },
+    Ewma {
+        series_hash: [u8; 32],
+        lambda: f64,
+        estimate: Result<VolEstimate, SeriesError>,
+    },
+    Garch {
+        params_hash: [u8; 32],
+        series_hash: [u8; 32],
+        horizon: u32,
+        estimate: Result<VolEstimate, SeriesError>,
+    },
+    ThetaSchedule {
+        schedule: Result<ThetaSchedule, ThetaError>,
+    },
+}
+
+/// The decoded public values of a proof.
+#[derive(Clone, Debug)]
+pub struct PublicValues {
+    pub header: Header,
+    /// `None` when the request failed the bounds policy check, which commits nothing after
+    /// the header.
+    pub outputs: Option<Outputs>,
+}
+
+fn append<T: Serialize>(bytes: &mut Vec<u8>, value: &T) {
+    bincode::serialize_into(bytes, value).unwrap();
+}
+
+impl PublicValues {
+    /// Reads the values committed for `request` off `public_values`, in order. Panics on any
+    /// schema version but [`SCHEMA_VERSION`], whose layout would be misread.
+    pub fn decode(request: &PricingRequest, public_values: &mut SP1PublicValues) -> Self {
+        let header = public_values.read::<Header>();
+        assert_eq!(
+            header.schema_version, SCHEMA_VERSION,
+            "public values use schema version {}, this script reads version {}",
+            header.schema_version, SCHEMA_VERSION
+        );
+
+        if header.check.is_err() {
+            return PublicValues { header, outputs: None };
+        }
+
+        let outputs = match request {
+            PricingRequest::BlackScholes(_) => {
+                let call = public_values.read::<f64>();
+                let put = public_values.read::<f64>();
+                Outputs::BlackScholes { call, put }
+            }
+            PricingRequest::ImpliedDividend(_) => {
+                let input = public_values.read::<ImpliedDividendInput>();
+                let implied = public_values.read::<Result<ImpliedDividend, ImpliedDividendError>>();
+                Outputs::ImpliedDividend { input, implied }
+            }
+            PricingRequest::TermStructure(_) => {
+                let structure = public_values.read::<Result<TermStructure, TermStructureError>>();
+                let outputs_digest = structure.is_ok().then(|| public_values.read::<[u8; 32]>());
+                Outputs::TermStructure {
+                    structure,
+                    outputs_digest,
+                }
+            }
+            PricingRequest::Quanto(_) => {
+                let input = public_values.read::<QuantoInput>();
+                let prices = public_values.read::<Result<QuantoPrices, QuantoError>>();
+                Outputs::Quanto { input, prices }
+            }
+            PricingRequest::Greeks(_) => {
+                let input = public_values.read::<GreeksInput>();
+                let greeks = public_values.read::<Greeks>();
+                Outputs::Greeks { input, greeks }
+            }
+            PricingRequest::Ewma(_) => {
+                let series_hash = public_values.read::<[u8; 32]>();
+                let lambda = public_values.read::<f64>();
+                let estimate = public_values.read::<Result<VolEstimate, SeriesError>>();
+                Outputs::Ewma {
+                    series_hash,
+                    lambda,
+                    estimate,
+                }
+            }
+            PricingRequest::Garch(_) => {
+                let params_hash = public_values.read::<[u8; 32]>();
+                let series_hash = public_values.read::<[u8; 32]>();
+                let horizon = public_values.read::<u32>();
+                let estimate = public_values.read::<Result<VolEstimate, SeriesError>>();
+                Outputs::Garch {
+                    params_hash,
+                    series_hash,
+                    horizon,
+                    estimate,
+                }
+            }
+            PricingRequest::ThetaSchedule(_) => {
+                let schedule = public_values.read::<Result<ThetaSchedule, ThetaError>>();
+                Outputs::ThetaSchedule { schedule }
+            }
+        };
+
+        PublicValues { header, outputs: Some(outputs) }
+    }
+
+    /// Encodes the values exactly as the program commits them.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        append(&mut bytes, &self.header);
+        match &self.outputs {
+            Some(Outputs::BlackScholes { call, put }) => {
+                append(&mut bytes, call);
+                append(&mut bytes, put);
+            }
+            Some(Outputs::ImpliedDividend { input, implied }) => {
+                append(&mut bytes, input);
+                append(&mut bytes, implied);
+            }
+            Some(Outputs::TermStructure {
+                structure,
+                outputs_digest,
+            }) => {
+                append(&mut bytes, structure);
+                if let Some(outputs_digest) = outputs_digest {
+                    append(&mut bytes, outputs_digest);
+                }
+            }
+            Some(Outputs::Quanto { input, prices }) => {
+                append(&mut bytes, input);
+                append(&mut bytes, prices);
+            }
+            Some(Outputs::Greeks { input, greeks }) => {
+                append(&mut bytes, input);
+                append(&mut bytes, greeks);
+            }
+            Some(Outputs::Ewma {
+                series_hash,
+                lambda,
+                estimate,
+            }) => {
+                append(&mut bytes, series_hash);
+                append(&mut bytes, lambda);
+                append(&mut bytes, estimate);
+            }
+            Some(Outputs::Garch {
+                params_hash,
+                series_hash,
+                horizon,
+                estimate,
+            }) => {
+                append(&mut bytes, params_hash);
+                append(&mut bytes, series_hash);
+                append(&mut bytes, horizon);
+                append(&mut bytes, estimate);
+            }
+            Some(Outputs::ThetaSchedule { schedule }) => {
+                append(&mut bytes, schedule);
+            }
+            None => {}
+        }
+        bytes
+    }
+}
+
+fn print_vol_estimate(label: &str, estimate: &Result<VolEstimate, SeriesError>) {
+    match estimate {
+        Ok(estimate) => {
+            println!("{} volatility: {:.6}", label, estimate.vol);
+            println!("Call option price: {:.4}", estimate.call);
+            println!("Put option price: {:.4}", estimate.put);
+        }
+        Err(err) => println!("Price series rejected: {:?}", err),
+    }
+}
+
+/// Prints decoded public values. For term structures, the only batch mode, the canonical
+/// encoding of the outputs is also written to `outputs_path`.
+pub fn print_summary(values: &PublicValues, outputs_path: Option<&str>) {
+    println!("Public values schema: v{}", values.header.schema_version);
+    println!("Request sha256: {}", hex::encode(values.header.input_hash));
+    println!(
+        "Available in Avail block #{} ({})",
+        values.header.inclusion.block_number,
+        hex::encode(values.header.inclusion.block_hash)
+    );
+    println!("Bounds policy: {}", hex::encode(values.header.policy_hash));
+    if let Err(violation) = values.header.check {
+        println!("Request rejected by the bounds policy: {:?}", violation);
+        return;
+    }
+    match &values.outputs {
+        Some(Outputs::BlackScholes { call, put }) => {
+            println!("Call option price: {:.4}", call);
+            println!("Put option price: {:.4}", put);
+        }
+        Some(Outputs::ImpliedDividend { input, implied }) => {
+            println!("Quotes: call {:.4}, put {:.4}", input.call, input.put);
+            match implied {
+                Ok(implied) => println!(
+                    "Implied dividend yield: {:.6} (residual {:e})",
+                    implied.dividend_yield, implied.residual
+                ),
+                Err(err) => println!("No implied dividend yield: {:?}", err),
+            }
+        }
+        Some(Outputs::TermStructure {
+            structure,
+            outputs_digest,
+        }) => {
+            let structure = match structure {
+                Ok(structure) => structure,
+                Err(err) => {
+                    println!("Term structure rejected: {:?}", err);
+                    return;
+                }
+            };
+            println!("{:>10} {:>12} {:>12}", "tenor", "call", "forward");
+            for point in &structure.points {
+                println!("{:>10.4} {:>12.4} {:>12.4}", point.time, point.call, point.forward);
+            }
+            if structure.calendar_arbitrage {
+                println!("Calendar arbitrage: longer-dated calls are cheaper than shorter-dated ones");
+            }
+
+            let outputs_digest = outputs_digest.expect("an accepted term structure commits its digest");
+            assert_eq!(
+                outputs_digest,
+                compute_outputs_digest(structure),
+                "committed outputs digest does not match the committed outputs"
+            );
+            println!("Outputs digest: 0x{}", hex::encode(outputs_digest));
+            if let Some(path) = outputs_path {
+                std::fs::write(path, bincode::serialize(structure).unwrap())
+                    .expect("writing the outputs failed");
+                println!("Canonical outputs written to {}", path);
+            }
+        }
+        Some(Outputs::Quanto { input, prices }) => {
+            println!(
+                "Quanto at conversion rate {:.4}, rho {:.4}, FX vol {:.4}",
+                input.conversion_rate, input.correlation, input.fx_vol
+            );
+            match prices {
+                Ok(prices) => {
+                    println!("Call option price: {:.4} (delta {:.4})", prices.call, prices.call_delta);
+                    println!("Put option price: {:.4} (delta {:.4})", prices.put, prices.put_delta);
+                }
+                Err(err) => println!("Quanto rejected: {:?}", err),
+            }
+        }
+        Some(Outputs::Greeks { input: _, greeks }) => {
+            println!("Call option price: {:.4}", greeks.call);
+            println!("Put option price: {:.4}", greeks.put);
+            for (name, greek) in [
+                ("Call delta", greeks.call_delta),
+                ("Put delta", greeks.put_delta),
+                ("Vega", greeks.vega),
+            ] {
+                println!("{}: {:.6} ({:?})", name, greek.value, greek.method);
+            }
+        }
+        Some(Outputs::Ewma {
+            series_hash,
+            lambda,
+            estimate,
+        }) => {
+            println!("Price series: sha256 0x{}", hex::encode(series_hash));
+            println!("EWMA lambda: {}", lambda);
+            print_vol_estimate("EWMA", estimate);
+        }
+        Some(Outputs::Garch {
+            params_hash,
+            series_hash,
+            horizon,
+            estimate,
+        }) => {
+            println!("GARCH(1,1) parameters: sha256 0x{}", hex::encode(params_hash));
+            println!("Price series: sha256 0x{}", hex::encode(series_hash));
+            println!("Forecast horizon: {} periods", horizon);
+            print_vol_estimate("GARCH(1,1)", estimate);
+        }
+        Some(Outputs::ThetaSchedule { schedule }) => {
+            let schedule = match schedule {
+                Ok(schedule) => schedule,
+                Err(err) => {
+                    println!("Theta schedule rejected: {:?}", err);
+                    return;
+                }
+            };
+            println!("Call option price: {:.4}", schedule.call);
+            println!("Put option price: {:.4}", schedule.put);
+            println!("{:>10} {:>12} {:>12}", "days", "call", "put");
+            for point in &schedule.points {
+                println!("{:>10} {:>12.4} {:>12.4}", point.offset_days, point.call, point.put);
+            }
+        }
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ELF;
+    use common::{BlackScholesInput, BoundsPolicy, DaInclusion};
+    use sp1_sdk::{ProverClient, SP1Stdin};
+
+    const SNAPSHOTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/snapshots");
+
+    /// Runs the program on `request` under `policy`, without proving. The snapshots do not
+    /// touch the chain, so every request claims the same inclusion.
+    fn execute(request: &PricingRequest, policy: &BoundsPolicy) -> SP1PublicValues {
+        let inclusion = DaInclusion {
+            block_hash: [0xab; 32],
+            block_number: 1_000_000,
+        };
+        let mut stdin = SP1Stdin::new();
+        stdin.write_slice(&crate::input::to_bytes(request));
+        stdin.write(policy);
+        stdin.write(&inclusion);
+        ProverClient::new()
+            .execute(ELF, stdin)
+            .expect("execution failed")
+    }
+
+    /// Every example in `requests/`, named after its file, and one the shipped policy rejects.
+    fn snapshot_requests() -> Vec<(String, PricingRequest)> {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/requests");
+        let mut requests: Vec<(String, PricingRequest)> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| {
+                let path = entry.unwrap().path();
+                let name = path.file_stem().unwrap().to_str().unwrap().to_string();
+                (name, crate::input::load(path.to_str().unwrap()))
+            })
+            .collect();
+        requests.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let rejected = BlackScholesInput {
+            iv: 50.0,
+            ..BlackScholesInput::default()
+        };
+        requests.push(("policy-rejection".to_string(), PricingRequest::BlackScholes(rejected)));
+        requests
+    }
+
+    /// Compares `bytes` with `snapshots/<name>.hex`: the schema version it was blessed at, then
+    /// the public values in hex. Under `BLESS_SNAPSHOTS=1` the snapshot is rewritten instead;
+    /// `snapshots/README.md` says when that needs a schema version bump.
+    fn assert_snapshot(name: &str, bytes: &[u8]) {
+        let path = format!("{}/{}.hex", SNAPSHOTS, name);
+        let encoded = hex::encode(bytes);
+        if std::env::var_os("BLESS_SNAPSHOTS").is_some() {
+            std::fs::write(&path, format!("{}\n{}\n", SCHEMA_VERSION, encoded)).unwrap();
+            return;
+        }
+
+        let blessed = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!("{}: no snapshot; run with BLESS_SNAPSHOTS=1 and commit {}", name, path)
+        });
+        let (version, blessed) = blessed.split_once('\n').expect("malformed snapshot");
+        assert_eq!(
+            version,
+            SCHEMA_VERSION.to_string(),
+            "{}: snapshot was blessed at another schema version",
+            name
+        );
+        assert_eq!(blessed.trim(), encoded, "{}: public values differ from the snapshot", name);
+    }
+
+    #[test]
+    fn public_values_match_their_snapshots() {
+        let policy = crate::policy::load(concat!(env!("CARGO_MANIFEST_DIR"), "/policy.toml"));
+        for (name, request) in snapshot_requests() {
+            let bytes = execute(&request, &policy).as_slice().to_vec();
+            assert_snapshot(&name, &bytes);
+
+            let decoded = PublicValues::decode(&request, &mut SP1PublicValues::from(bytes.as_slice()));
+            // The decoder consumes exactly what the program committed.
+            assert_eq!(decoded.encode(), bytes, "{}: decode and encode disagree", name);
+        }
+    }
+
+    fn committed_term_structure(options: &[BlackScholesInput]) -> (TermStructure, [u8; 32]) {
+        let policy = crate::policy::load(concat!(env!("CARGO_MANIFEST_DIR"), "/policy.toml"));
+        let request = PricingRequest::TermStructure(options.to_vec());
+        let mut public_values = execute(&request, &policy);
+        match PublicValues::decode(&request, &mut public_values).outputs {
+            Some(Outputs::TermStructure {
+                structure: Ok(structure),
+                outputs_digest: Some(outputs_digest),
+            }) => (structure, outputs_digest),
+            outputs => panic!("unexpected outputs {:?}", outputs),
+        }
+    }
+
+    /// Both example batches, a single option, and a batch with negative-rate pillars.
+    fn term_structure_batches() -> Vec<Vec<BlackScholesInput>> {
+        let load = |name: &str| {
+            let path = format!("{}/requests/{}", env!("CARGO_MANIFEST_DIR"), name);
+            match crate::input::load(&path) {
+                PricingRequest::TermStructure(options) => options,
+                request => panic!("{} is not a term structure: {:?}", name, request),
+            }
+        };
+        let deep = |time, rate| BlackScholesInput {
+            price: 100.0,
+            strike: 50.0,
+            iv: 0.01,
+            time,
+            rate,
+        };
+        vec![
+            load("term-structure.json"),
+            load("term-structure-calendar-arbitrage.json"),
+            vec![BlackScholesInput::default()],
+            vec![deep(1.0, -0.01), deep(2.0, -0.01), deep(3.0, 0.05)],
+        ]
+    }
+
+    /// The canonical encoding written out field by field, as a contract would rebuild it: each
+    /// list as a u64 length and its elements, every f64 little-endian, and the flag as a byte.
+    fn expected_encoding(structure: &TermStructure) -> Vec<u8> {
+        let mut bytes = (structure.prices.len() as u64).to_le_bytes().to_vec();
+        for (call, put) in &structure.prices {
+            bytes.extend(call.to_le_bytes());
+            bytes.extend(put.to_le_bytes());
+        }
+        bytes.extend((structure.points.len() as u64).to_le_bytes());
+        for point in &structure.points {
+            bytes.extend(point.time.to_le_bytes());
+            bytes.extend(point.call.to_le_bytes());
+            bytes.extend(point.forward.to_le_bytes());
+        }
+        bytes.push(structure.calendar_arbitrage as u8);
+        bytes
+    }
+
+    #[test]
+    fn committed_outputs_digest_is_the_canonical_one() {
+        use sha2::{Digest, Sha256};
+
+        for options in term_structure_batches() {
+            let (structure, outputs_digest) = committed_term_structure(&options);
+            let expected: [u8; 32] = Sha256::digest(expected_encoding(&structure)).into();
+            assert_eq!(outputs_digest, expected);
+            assert_eq!(compute_outputs_digest(&structure), expected);
+        }
+    }
+
+    #[test]
+    fn perturbing_any_price_changes_the_digest() {
+        for options in term_structure_batches() {
+            let (structure, outputs_digest) = committed_term_structure(&options);
+            // Every committed price, one ulp off.
+            for i in 0..structure.prices.len() {
+                for leg in 0..2 {
+                    let mut perturbed = structure.clone();
+                    let (call, put) = &mut perturbed.prices[i];
+                    let price = if leg == 0 { call } else { put };
+                    *price = f64::from_bits(price.to_bits() + 1);
+                    assert_ne!(compute_outputs_digest(&perturbed), outputs_digest);
+                }
+            }
+            // Every option's rate pillar, re-executed. Unlike the vol, it moves even the
+            // near-intrinsic deep in-the-money prices.
+            for i in 0..options.len() {
+                let mut perturbed = options.clone();
+                perturbed[i].rate += 0.001;
+                assert_ne!(committed_term_structure(&perturbed).1, outputs_digest);
+            }
+        }
+    }
+}